@@ -0,0 +1,31 @@
+use crate::callbacks::{CallbackDispatcher, CallbackStorage};
+use crate::steam::SteamId;
+use steamworks_sys as sys;
+
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#AvatarImageLoaded_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct AvatarImageLoaded {
+    pub steam_id: SteamId,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct AvatarImageLoadedDispatcher(CallbackStorage<AvatarImageLoaded>);
+
+impl CallbackDispatcher for AvatarImageLoadedDispatcher {
+    type RawCallbackData = sys::AvatarImageLoaded_t;
+    type MappedCallbackData = AvatarImageLoaded;
+
+    fn storage(&self) -> &CallbackStorage<AvatarImageLoaded> {
+        &self.0
+    }
+
+    fn map_callback_data(raw: &sys::AvatarImageLoaded_t) -> AvatarImageLoaded {
+        AvatarImageLoaded {
+            steam_id: raw.m_steamID.into(),
+            width: raw.m_iWide as u32,
+            height: raw.m_iTall as u32,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::callbacks::{CallbackDispatcher, CallbackStorage};
+use crate::steam::AppId;
+use steamworks_sys as sys;
+
+/// <https://partner.steamgames.com/doc/api/ISteamApps#DlcInstalled_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct DlcInstalled {
+    pub app_id: AppId,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DlcInstalledDispatcher(CallbackStorage<DlcInstalled>);
+
+impl CallbackDispatcher for DlcInstalledDispatcher {
+    type RawCallbackData = sys::DlcInstalled_t;
+    type MappedCallbackData = DlcInstalled;
+
+    fn storage(&self) -> &CallbackStorage<DlcInstalled> {
+        &self.0
+    }
+
+    fn map_callback_data(raw: &sys::DlcInstalled_t) -> DlcInstalled {
+        DlcInstalled {
+            app_id: raw.m_nAppID.into(),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::callbacks::{CallbackDispatcher, CallbackStorage};
+use crate::steam::ugc::PublishedFileId;
+use crate::steam::{AppId, SteamResult};
+use steamworks_sys as sys;
+
+/// <https://partner.steamgames.com/doc/api/ISteamUGC#DownloadItemResult_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct DownloadItemResult {
+    pub app_id: AppId,
+    pub published_file_id: PublishedFileId,
+    pub steam_result: SteamResult,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DownloadItemResultDispatcher(CallbackStorage<DownloadItemResult>);
+
+impl CallbackDispatcher for DownloadItemResultDispatcher {
+    type RawCallbackData = sys::DownloadItemResult_t;
+    type MappedCallbackData = DownloadItemResult;
+
+    fn storage(&self) -> &CallbackStorage<DownloadItemResult> {
+        &self.0
+    }
+
+    fn map_callback_data(raw: &sys::DownloadItemResult_t) -> DownloadItemResult {
+        DownloadItemResult {
+            app_id: raw.m_unAppID.into(),
+            published_file_id: raw.m_nPublishedFileId.into(),
+            steam_result: SteamResult::from_inner_or_fail(raw.m_eResult),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::callbacks::{CallbackDispatcher, CallbackStorage};
+use crate::steam::{AppId, SteamId};
+use steamworks_sys as sys;
+
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#FriendRichPresenceUpdate_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct FriendRichPresenceUpdate {
+    pub steam_id: SteamId,
+    pub app_id: AppId,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct FriendRichPresenceUpdateDispatcher(CallbackStorage<FriendRichPresenceUpdate>);
+
+impl CallbackDispatcher for FriendRichPresenceUpdateDispatcher {
+    type RawCallbackData = sys::FriendRichPresenceUpdate_t;
+    type MappedCallbackData = FriendRichPresenceUpdate;
+
+    fn storage(&self) -> &CallbackStorage<FriendRichPresenceUpdate> {
+        &self.0
+    }
+
+    fn map_callback_data(raw: &sys::FriendRichPresenceUpdate_t) -> FriendRichPresenceUpdate {
+        FriendRichPresenceUpdate {
+            steam_id: raw.m_steamIDFriend.into(),
+            app_id: raw.m_nAppID.into(),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::callbacks::{CallbackDispatcher, CallbackStorage};
+use crate::steam::SteamId;
+use steamworks_sys as sys;
+
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#GameLobbyJoinRequested_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct GameLobbyJoinRequested {
+    pub lobby: SteamId,
+    /// The friend the user joined through, if any.
+    pub friend: Option<SteamId>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct GameLobbyJoinRequestedDispatcher(CallbackStorage<GameLobbyJoinRequested>);
+
+impl CallbackDispatcher for GameLobbyJoinRequestedDispatcher {
+    type RawCallbackData = sys::GameLobbyJoinRequested_t;
+    type MappedCallbackData = GameLobbyJoinRequested;
+
+    fn storage(&self) -> &CallbackStorage<GameLobbyJoinRequested> {
+        &self.0
+    }
+
+    fn map_callback_data(raw: &sys::GameLobbyJoinRequested_t) -> GameLobbyJoinRequested {
+        let friend = SteamId::from(raw.m_steamIDFriend);
+
+        GameLobbyJoinRequested {
+            lobby: raw.m_steamIDLobby.into(),
+            friend: Some(friend).filter(|id| id.as_u64() != 0),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::callbacks::{CallbackDispatcher, CallbackStorage};
+use steamworks_sys as sys;
+
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#GameOverlayActivated_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct GameOverlayActivated {
+    /// Whether the overlay was opened, as opposed to closed.
+    pub active: bool,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct GameOverlayActivatedDispatcher(CallbackStorage<GameOverlayActivated>);
+
+impl CallbackDispatcher for GameOverlayActivatedDispatcher {
+    type RawCallbackData = sys::GameOverlayActivated_t;
+    type MappedCallbackData = GameOverlayActivated;
+
+    fn storage(&self) -> &CallbackStorage<GameOverlayActivated> {
+        &self.0
+    }
+
+    fn map_callback_data(raw: &sys::GameOverlayActivated_t) -> GameOverlayActivated {
+        GameOverlayActivated {
+            active: raw.m_bActive != 0,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::callbacks::{CallbackDispatcher, CallbackStorage};
+use crate::steam::SteamId;
+use crate::string_ext::FromUtf8NulTruncating;
+use steamworks_sys as sys;
+
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#GameRichPresenceJoinRequested_t>
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct GameRichPresenceJoinRequested {
+    /// The friend whose game the user is joining, if any.
+    pub steam_id: Option<SteamId>,
+    /// The value of the friend's `connect` rich presence key. Invalid UTF-8 is replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    pub connect: String,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct GameRichPresenceJoinRequestedDispatcher(
+    CallbackStorage<GameRichPresenceJoinRequested>,
+);
+
+impl CallbackDispatcher for GameRichPresenceJoinRequestedDispatcher {
+    type RawCallbackData = sys::GameRichPresenceJoinRequested_t;
+    type MappedCallbackData = GameRichPresenceJoinRequested;
+
+    fn storage(&self) -> &CallbackStorage<GameRichPresenceJoinRequested> {
+        &self.0
+    }
+
+    fn map_callback_data(
+        raw: &sys::GameRichPresenceJoinRequested_t,
+    ) -> GameRichPresenceJoinRequested {
+        let steam_id = SteamId::from(raw.m_steamIDFriend);
+
+        GameRichPresenceJoinRequested {
+            steam_id: Some(steam_id).filter(|id| id.as_u64() != 0),
+            connect: String::from_utf8_nul_truncating(&raw.m_rgchConnect[..])
+                .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()),
+        }
+    }
+}
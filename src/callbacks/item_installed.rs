@@ -0,0 +1,30 @@
+use crate::callbacks::{CallbackDispatcher, CallbackStorage};
+use crate::steam::ugc::PublishedFileId;
+use crate::steam::AppId;
+use steamworks_sys as sys;
+
+/// <https://partner.steamgames.com/doc/api/ISteamUGC#ItemInstalled_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ItemInstalled {
+    pub app_id: AppId,
+    pub published_file_id: PublishedFileId,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ItemInstalledDispatcher(CallbackStorage<ItemInstalled>);
+
+impl CallbackDispatcher for ItemInstalledDispatcher {
+    type RawCallbackData = sys::ItemInstalled_t;
+    type MappedCallbackData = ItemInstalled;
+
+    fn storage(&self) -> &CallbackStorage<ItemInstalled> {
+        &self.0
+    }
+
+    fn map_callback_data(raw: &sys::ItemInstalled_t) -> ItemInstalled {
+        ItemInstalled {
+            app_id: raw.m_unAppID.into(),
+            published_file_id: raw.m_nPublishedFileId.into(),
+        }
+    }
+}
@@ -1,5 +1,14 @@
+pub use avatar_image_loaded::*;
+pub use dlc_installed::*;
+pub use download_item_result::*;
+pub use friend_rich_presence_update::*;
+pub use game_lobby_join_requested::*;
+pub use game_overlay_activated::*;
+pub use game_rich_presence_join_requested::*;
+pub use item_installed::*;
 pub use persona_state_change::*;
 
+use crate::Client;
 use az::WrappingCast;
 use futures::Stream;
 use parking_lot::Mutex;
@@ -8,22 +17,61 @@ use std::convert::TryFrom;
 use std::mem;
 use steamworks_sys as sys;
 
+mod avatar_image_loaded;
+mod dlc_installed;
+mod download_item_result;
+mod friend_rich_presence_update;
+mod game_lobby_join_requested;
+mod game_overlay_activated;
+mod game_rich_presence_join_requested;
+mod item_installed;
 mod persona_state_change;
 
 pub(crate) type CallbackStorage<T> =
     Mutex<DenseSlotMap<slotmap::DefaultKey, futures::channel::mpsc::UnboundedSender<T>>>;
 
-pub(crate) unsafe fn dispatch_callbacks(
-    callback_dispatchers: &CallbackDispatchers,
-    callback_msg: sys::CallbackMsg_t,
-) {
+pub(crate) unsafe fn dispatch_callbacks(client: &Client, callback_msg: sys::CallbackMsg_t) {
+    let callback_dispatchers = &client.0.callback_dispatchers;
     match callback_msg.m_iCallback.wrapping_cast() {
-        sys::PersonaStateChange_t_k_iCallback => callback_dispatchers
-            .persona_state_change
-            .dispatch(callback_msg.m_pubParam, callback_msg.m_cubParam),
+        sys::PersonaStateChange_t_k_iCallback => {
+            let change = PersonaStateChangeDispatcher::read(
+                callback_msg.m_pubParam,
+                callback_msg.m_cubParam,
+            );
+            // Update the cache first, so that receivers of the change see the new name
+            client.0.persona_name_cache.update(client, change);
+            callback_dispatchers.persona_state_change.send(change);
+        }
         sys::SteamShutdown_t_k_iCallback => callback_dispatchers
             .steam_shutdown
             .dispatch(callback_msg.m_pubParam, callback_msg.m_cubParam),
+        sys::ItemInstalled_t_k_iCallback => callback_dispatchers
+            .item_installed
+            .dispatch(callback_msg.m_pubParam, callback_msg.m_cubParam),
+        sys::DownloadItemResult_t_k_iCallback => callback_dispatchers
+            .download_item_result
+            .dispatch(callback_msg.m_pubParam, callback_msg.m_cubParam),
+        sys::AvatarImageLoaded_t_k_iCallback => callback_dispatchers
+            .avatar_image_loaded
+            .dispatch(callback_msg.m_pubParam, callback_msg.m_cubParam),
+        sys::FriendRichPresenceUpdate_t_k_iCallback => callback_dispatchers
+            .friend_rich_presence_update
+            .dispatch(callback_msg.m_pubParam, callback_msg.m_cubParam),
+        sys::GameRichPresenceJoinRequested_t_k_iCallback => callback_dispatchers
+            .game_rich_presence_join_requested
+            .dispatch(callback_msg.m_pubParam, callback_msg.m_cubParam),
+        sys::GameLobbyJoinRequested_t_k_iCallback => callback_dispatchers
+            .game_lobby_join_requested
+            .dispatch(callback_msg.m_pubParam, callback_msg.m_cubParam),
+        sys::GameOverlayActivated_t_k_iCallback => callback_dispatchers
+            .game_overlay_activated
+            .dispatch(callback_msg.m_pubParam, callback_msg.m_cubParam),
+        sys::NewUrlLaunchParameters_t_k_iCallback => callback_dispatchers
+            .new_url_launch_parameters
+            .dispatch(callback_msg.m_pubParam, callback_msg.m_cubParam),
+        sys::DlcInstalled_t_k_iCallback => callback_dispatchers
+            .dlc_installed
+            .dispatch(callback_msg.m_pubParam, callback_msg.m_cubParam),
         _ => {}
     }
 }
@@ -40,6 +88,15 @@ pub(crate) fn register_to_receive_callback<T: Clone + Send + 'static>(
 pub(crate) struct CallbackDispatchers {
     pub(crate) persona_state_change: PersonaStateChangeDispatcher,
     pub(crate) steam_shutdown: SteamShutdownDispatcher,
+    pub(crate) item_installed: ItemInstalledDispatcher,
+    pub(crate) download_item_result: DownloadItemResultDispatcher,
+    pub(crate) avatar_image_loaded: AvatarImageLoadedDispatcher,
+    pub(crate) friend_rich_presence_update: FriendRichPresenceUpdateDispatcher,
+    pub(crate) game_rich_presence_join_requested: GameRichPresenceJoinRequestedDispatcher,
+    pub(crate) game_lobby_join_requested: GameLobbyJoinRequestedDispatcher,
+    pub(crate) game_overlay_activated: GameOverlayActivatedDispatcher,
+    pub(crate) dlc_installed: DlcInstalledDispatcher,
+    pub(crate) new_url_launch_parameters: NewUrlLaunchParametersDispatcher,
 }
 
 impl CallbackDispatchers {
@@ -56,6 +113,10 @@ pub(crate) trait CallbackDispatcher {
     fn map_callback_data(raw: &Self::RawCallbackData) -> Self::MappedCallbackData;
 
     unsafe fn dispatch(&self, callback_data: *const u8, callback_data_len: i32) {
+        self.send(Self::read(callback_data, callback_data_len));
+    }
+
+    unsafe fn read(callback_data: *const u8, callback_data_len: i32) -> Self::MappedCallbackData {
         assert!(!callback_data.is_null());
         assert_eq!(
             callback_data.align_offset(mem::align_of::<Self::RawCallbackData>()),
@@ -67,8 +128,10 @@ pub(crate) trait CallbackDispatcher {
         );
 
         let raw = &*(callback_data as *const Self::RawCallbackData);
-        let mapped = Self::map_callback_data(raw);
+        Self::map_callback_data(raw)
+    }
 
+    fn send(&self, mapped: Self::MappedCallbackData) {
         let mut storage = self.storage().lock();
         storage.retain(|_key, tx| match tx.unbounded_send(mapped.clone()) {
             Err(e) if e.is_disconnected() => false,
@@ -91,3 +154,17 @@ impl CallbackDispatcher for SteamShutdownDispatcher {
 
     fn map_callback_data(_raw: &sys::SteamShutdown_t) {}
 }
+
+#[derive(Debug, Default)]
+pub(crate) struct NewUrlLaunchParametersDispatcher(CallbackStorage<()>);
+
+impl CallbackDispatcher for NewUrlLaunchParametersDispatcher {
+    type RawCallbackData = sys::NewUrlLaunchParameters_t;
+    type MappedCallbackData = ();
+
+    fn storage(&self) -> &CallbackStorage<()> {
+        &self.0
+    }
+
+    fn map_callback_data(_raw: &sys::NewUrlLaunchParameters_t) {}
+}
@@ -36,10 +36,13 @@ pub use steam::*;
 use crate::callbacks::CallbackDispatchers;
 use atomic::Atomic;
 use az::WrappingCast;
+use chrono::{DateTime, Utc};
 use derive_more::Deref;
 use fnv::FnvHashMap;
 use futures::future::BoxFuture;
 use futures::{FutureExt, Stream};
+use futures_intrusive::timer::{StdClock, Timer, TimerFuture, TimerService};
+use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use snafu::ensure;
 use static_assertions::assert_impl_all;
@@ -47,6 +50,7 @@ use std::convert::TryInto;
 use std::ffi::{c_void, CStr};
 use std::mem::{self, MaybeUninit};
 use std::os::raw::c_char;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use std::{env, ptr, thread};
@@ -69,6 +73,8 @@ enum SteamApiState {
 
 static STEAM_API_STATE: Atomic<SteamApiState> = Atomic::new(SteamApiState::Stopped);
 
+static CLOCK: Lazy<StdClock> = Lazy::new(StdClock::new);
+
 /// The core type of this crate, representing an initialized Steamworks API.
 ///
 /// It's a handle that can be cheaply cloned.
@@ -82,12 +88,15 @@ struct ClientInner {
     callback_dispatchers: CallbackDispatchers,
     call_result_handles:
         Mutex<FnvHashMap<sys::SteamAPICall_t, futures::channel::oneshot::Sender<Vec<u8>>>>,
+    apps: SteamworksInterface<sys::ISteamApps>,
     friends: SteamworksInterface<sys::ISteamFriends>,
+    persona_name_cache: friends::PersonaNameCache,
     remote_storage: SteamworksInterface<sys::ISteamRemoteStorage>,
     ugc: SteamworksInterface<sys::ISteamUGC>,
     user: SteamworksInterface<sys::ISteamUser>,
     user_stats: SteamworksInterface<sys::ISteamUserStats>,
     utils: SteamworksInterface<sys::ISteamUtils>,
+    timer: TimerService,
 }
 
 #[derive(Debug, Copy, Clone, Deref)]
@@ -140,12 +149,15 @@ impl Client {
             Client(Arc::new(ClientInner {
                 callback_dispatchers: CallbackDispatchers::new(),
                 call_result_handles: Mutex::new(FnvHashMap::default()),
+                apps: SteamworksInterface(sys::SteamAPI_SteamApps_v008()),
                 friends: SteamworksInterface(sys::SteamAPI_SteamFriends_v017()),
+                persona_name_cache: friends::PersonaNameCache::new(),
                 remote_storage: SteamworksInterface(sys::SteamAPI_SteamRemoteStorage_v014()),
                 ugc: SteamworksInterface(sys::SteamAPI_SteamUGC_v014()),
                 user: SteamworksInterface(sys::SteamAPI_SteamUser_v021()),
                 user_stats: SteamworksInterface(sys::SteamAPI_SteamUserStats_v012()),
                 utils,
+                timer: TimerService::new(&*CLOCK),
             }))
         };
 
@@ -173,6 +185,644 @@ impl Client {
         ugc::QueryAllUgc::new(self.clone(), matching_ugc_type)
     }
 
+    /// Returns [`ugc::QueryUserUgc`], which follows the builder pattern, allowing you to configure
+    /// a query for a user's UGC before running it.
+    pub fn query_user_ugc(
+        &self,
+        account_id: AccountId,
+        list_type: ugc::UserUgcList,
+        matching_ugc_type: ugc::MatchingUgcType,
+        sort_order: ugc::UserUgcListSortOrder,
+    ) -> ugc::QueryUserUgc {
+        ugc::QueryUserUgc::new(
+            self.clone(),
+            account_id,
+            list_type,
+            matching_ugc_type,
+            sort_order,
+        )
+    }
+
+    /// Returns [`ugc::QueryUgcDetails`], which follows the builder pattern, allowing you to
+    /// configure a query for the details of specific workshop items before running it.
+    pub fn query_ugc_details(
+        &self,
+        published_file_ids: &[ugc::PublishedFileId],
+    ) -> ugc::QueryUgcDetails {
+        ugc::QueryUgcDetails::new(self.clone(), published_file_ids)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#CreateItem>
+    ///
+    /// The new item is empty and hidden until it's updated using
+    /// [`start_item_update`](Self::start_item_update).
+    pub fn create_item(
+        &self,
+        consumer_app_id: AppId,
+        file_type: ugc::WorkshopFileType,
+    ) -> BoxFuture<'_, Result<ugc::CreatedItem, ugc::CreateItemError>> {
+        ugc::create_item(self, consumer_app_id, file_type).boxed()
+    }
+
+    /// Returns [`ugc::ItemUpdate`], which follows the builder pattern, allowing you to configure
+    /// an update to a workshop item before submitting it.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#StartItemUpdate>
+    pub fn start_item_update(
+        &self,
+        consumer_app_id: AppId,
+        published_file_id: ugc::PublishedFileId,
+    ) -> ugc::ItemUpdate {
+        ugc::ItemUpdate::new(self.clone(), consumer_app_id, published_file_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SubscribeItem>
+    pub fn subscribe_item(
+        &self,
+        published_file_id: ugc::PublishedFileId,
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::subscribe_item(self, published_file_id).boxed()
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#UnsubscribeItem>
+    pub fn unsubscribe_item(
+        &self,
+        published_file_id: ugc::PublishedFileId,
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::unsubscribe_item(self, published_file_id).boxed()
+    }
+
+    /// Returns the workshop items the current user is subscribed to, for the current app.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#GetSubscribedItems>
+    pub fn subscribed_items(&self) -> Vec<ugc::PublishedFileId> {
+        ugc::subscribed_items(self)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#GetItemState>
+    pub fn item_state(&self, published_file_id: ugc::PublishedFileId) -> ugc::ItemState {
+        ugc::item_state(self, published_file_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#GetItemInstallInfo>
+    ///
+    /// Returns `None` if the item is not installed.
+    pub fn item_install_info(
+        &self,
+        published_file_id: ugc::PublishedFileId,
+    ) -> Option<ugc::ItemInstallInfo> {
+        ugc::item_install_info(self, published_file_id)
+    }
+
+    /// Downloads or updates a workshop item, completing once the download has finished.
+    ///
+    /// If `high_priority` is `true`, the item is downloaded immediately, pausing other downloads.
+    /// Use [`item_download_progress_updates`](Self::item_download_progress_updates) to follow the
+    /// download's progress. See <https://partner.steamgames.com/doc/api/ISteamUGC#DownloadItem>.
+    pub fn download_item(
+        &self,
+        published_file_id: ugc::PublishedFileId,
+        high_priority: bool,
+    ) -> BoxFuture<'_, Result<(), ugc::DownloadItemError>> {
+        ugc::download_item(self, published_file_id, high_priority).boxed()
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#GetItemDownloadInfo>
+    ///
+    /// Returns `None` if there is no download information available for the item.
+    pub fn item_download_progress(
+        &self,
+        published_file_id: ugc::PublishedFileId,
+    ) -> Option<ugc::ItemDownloadProgress> {
+        ugc::item_download_progress(self, published_file_id)
+    }
+
+    /// Polls [`item_download_progress`](Self::item_download_progress) every `interval`, yielding
+    /// the progress until the download has finished.
+    ///
+    /// The stream waits for Steam to start reporting progress, and ends once Steam reports the
+    /// download's result, so it can be created before or after starting the download:
+    ///
+    /// ```no_run
+    /// # let client: steamworks::Client = unimplemented!();
+    /// # let published_file_id: steamworks::ugc::PublishedFileId = unimplemented!();
+    /// use futures::{future, StreamExt};
+    /// use std::time::Duration;
+    ///
+    /// # async {
+    /// let progress = client
+    ///     .item_download_progress_updates(published_file_id, Duration::from_millis(100))
+    ///     .for_each(|p| {
+    ///         println!("{}/{} bytes", p.bytes_downloaded, p.bytes_total);
+    ///         future::ready(())
+    ///     });
+    /// let download = client.download_item(published_file_id, true);
+    /// let (result, ()) = future::join(download, progress).await;
+    /// # };
+    /// ```
+    pub fn item_download_progress_updates(
+        &self,
+        published_file_id: ugc::PublishedFileId,
+        interval: Duration,
+    ) -> impl Stream<Item = ugc::ItemDownloadProgress> + Send {
+        ugc::item_download_progress_updates(self.clone(), published_file_id, interval)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetUserItemVote>
+    pub fn set_user_item_vote(
+        &self,
+        published_file_id: ugc::PublishedFileId,
+        vote_up: bool,
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::set_user_item_vote(self, published_file_id, vote_up).boxed()
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#GetUserItemVote>
+    pub fn get_user_item_vote(
+        &self,
+        published_file_id: ugc::PublishedFileId,
+    ) -> BoxFuture<'_, Result<ugc::UserItemVote, ugc::UgcCallError>> {
+        ugc::get_user_item_vote(self, published_file_id).boxed()
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddItemToFavorites>
+    pub fn add_item_to_favorites(
+        &self,
+        app_id: AppId,
+        published_file_id: ugc::PublishedFileId,
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::add_item_to_favorites(self, app_id, published_file_id).boxed()
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#RemoveItemFromFavorites>
+    pub fn remove_item_from_favorites(
+        &self,
+        app_id: AppId,
+        published_file_id: ugc::PublishedFileId,
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::remove_item_from_favorites(self, app_id, published_file_id).boxed()
+    }
+
+    /// Adds `child` as a dependency of `parent`, e.g. to add an item to a collection.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddDependency>
+    pub fn add_dependency(
+        &self,
+        parent: ugc::PublishedFileId,
+        child: ugc::PublishedFileId,
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::add_dependency(self, parent, child).boxed()
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#RemoveDependency>
+    pub fn remove_dependency(
+        &self,
+        parent: ugc::PublishedFileId,
+        child: ugc::PublishedFileId,
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::remove_dependency(self, parent, child).boxed()
+    }
+
+    /// Makes the item depend on an app, such as a DLC.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddAppDependency>
+    pub fn add_app_dependency(
+        &self,
+        published_file_id: ugc::PublishedFileId,
+        app_id: AppId,
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::add_app_dependency(self, published_file_id, app_id).boxed()
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#RemoveAppDependency>
+    pub fn remove_app_dependency(
+        &self,
+        published_file_id: ugc::PublishedFileId,
+        app_id: AppId,
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::remove_app_dependency(self, published_file_id, app_id).boxed()
+    }
+
+    /// Permanently deletes a workshop item the current user owns.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#DeleteItem>
+    pub fn delete_item(
+        &self,
+        published_file_id: ugc::PublishedFileId,
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::delete_item(self, published_file_id).boxed()
+    }
+
+    /// Starts tracking playtime for the given workshop items, e.g. the mods that are currently
+    /// active. Tracking is stopped automatically when the game exits.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#StartPlaytimeTracking>
+    pub fn start_playtime_tracking(
+        &self,
+        published_file_ids: &[ugc::PublishedFileId],
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::start_playtime_tracking(self, published_file_ids.to_vec()).boxed()
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#StopPlaytimeTracking>
+    pub fn stop_playtime_tracking(
+        &self,
+        published_file_ids: &[ugc::PublishedFileId],
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::stop_playtime_tracking(self, published_file_ids.to_vec()).boxed()
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#StopPlaytimeTrackingForAllItems>
+    pub fn stop_playtime_tracking_for_all_items(
+        &self,
+    ) -> BoxFuture<'_, Result<(), ugc::UgcCallError>> {
+        ugc::stop_playtime_tracking_for_all_items(self).boxed()
+    }
+
+    /// Returns the users that match any of the given flags, such as the current user's regular
+    /// friends with [`FriendFlags::IMMEDIATE`](friends::FriendFlags::IMMEDIATE).
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetFriendCount>
+    pub fn friends(&self, flags: friends::FriendFlags) -> Vec<SteamId> {
+        friends::friends(self, flags)
+    }
+
+    /// Fetches the persona names of several users at once, in the same order as `steam_ids`.
+    ///
+    /// Names are cached and kept up to date as they change, so repeated lookups are cheap. The names
+    /// of users whose information doesn't arrive within `timeout` are returned as `None`.
+    pub fn persona_names(
+        &self,
+        steam_ids: &[SteamId],
+        timeout: Duration,
+    ) -> BoxFuture<'_, Vec<Option<String>>> {
+        friends::persona_names(self, steam_ids.to_vec(), Some(timeout)).boxed()
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetFriendRelationship>
+    pub fn friend_relationship(&self, steam_id: SteamId) -> friends::FriendRelationship {
+        friends::friend_relationship(self, steam_id)
+    }
+
+    /// Returns the persona state of a friend, or of a user the current user shares a lobby,
+    /// group or game server with.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetFriendPersonaState>
+    pub fn persona_state(&self, steam_id: SteamId) -> friends::PersonaState {
+        friends::persona_state(self, steam_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetFriendGamePlayed>
+    ///
+    /// Returns `None` if the user isn't playing a game.
+    pub fn friend_game_played(&self, steam_id: SteamId) -> Option<friends::FriendGameInfo> {
+        friends::friend_game_played(self, steam_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetFriendSteamLevel>
+    ///
+    /// Returns `None` if the user's Steam level isn't available yet.
+    pub fn steam_level(&self, steam_id: SteamId) -> Option<u32> {
+        friends::steam_level(self, steam_id)
+    }
+
+    /// Returns the nickname the current user has given to a player.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetPlayerNickname>
+    pub fn player_nickname(&self, steam_id: SteamId) -> Option<String> {
+        friends::player_nickname(self, steam_id)
+    }
+
+    /// Sets a rich presence key for the current user, which is shown to their friends. Setting a
+    /// key to an empty value removes it.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#SetRichPresence>
+    pub fn set_rich_presence(
+        &self,
+        key: impl Into<Vec<u8>>,
+        value: impl Into<Vec<u8>>,
+    ) -> Result<(), friends::SetRichPresenceError> {
+        friends::set_rich_presence(self, key.into(), value.into())
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#ClearRichPresence>
+    pub fn clear_rich_presence(&self) {
+        friends::clear_rich_presence(self)
+    }
+
+    /// Returns the value of a friend's rich presence key, or `None` if the key isn't set. Keys
+    /// containing nul bytes are never set, since [`set_rich_presence`](Self::set_rich_presence)
+    /// rejects them.
+    ///
+    /// The rich presence of users who aren't friends is only available after requesting it with
+    /// [`request_friend_rich_presence`](Self::request_friend_rich_presence). See
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetFriendRichPresence>.
+    pub fn friend_rich_presence(&self, steam_id: SteamId, key: &str) -> Option<String> {
+        friends::friend_rich_presence(self, steam_id, key)
+    }
+
+    /// Returns the rich presence keys a friend has set.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetFriendRichPresenceKeyByIndex>
+    pub fn friend_rich_presence_keys(&self, steam_id: SteamId) -> Vec<String> {
+        friends::friend_rich_presence_keys(self, steam_id)
+    }
+
+    /// Requests the rich presence of a user, completing once it has been updated.
+    ///
+    /// Returns `false` if no update arrived within `timeout`. See
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#RequestFriendRichPresence>.
+    pub fn request_friend_rich_presence(
+        &self,
+        steam_id: SteamId,
+        timeout: Duration,
+    ) -> BoxFuture<'_, bool> {
+        friends::request_friend_rich_presence(self, steam_id, timeout).boxed()
+    }
+
+    /// Marks a user as someone the current user has played with, which adds them to the user's
+    /// recent players list.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#SetPlayedWith>
+    pub fn set_played_with(&self, steam_id: SteamId) {
+        friends::set_played_with(self, steam_id)
+    }
+
+    /// Returns the users the current user has recently played with.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetCoplayFriend>
+    pub fn coplay_friends(&self) -> Vec<friends::CoplayEntry> {
+        friends::coplay_friends(self)
+    }
+
+    /// Returns when and in which app the current user last played with a user, or `None` if they
+    /// haven't recently played together.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetFriendCoplayTime>
+    pub fn coplay_entry(&self, steam_id: SteamId) -> Option<friends::CoplayEntry> {
+        friends::coplay_entry(self, steam_id)
+    }
+
+    /// Returns the Steam groups the current user is a member of.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetClanByIndex>
+    pub fn clans(&self) -> Vec<ClanId> {
+        clans::clans(self)
+    }
+
+    /// Returns the name of a Steam group. Names are only available for groups the current user is
+    /// a member of, or has otherwise interacted with.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetClanName>
+    pub fn clan_name(&self, clan_id: ClanId) -> String {
+        clans::clan_name(self, clan_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetClanTag>
+    pub fn clan_tag(&self, clan_id: ClanId) -> String {
+        clans::clan_tag(self, clan_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#IsClanPublic>
+    pub fn is_clan_public(&self, clan_id: ClanId) -> bool {
+        clans::is_clan_public(self, clan_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#IsClanOfficialGameGroup>
+    pub fn is_clan_official_game_group(&self, clan_id: ClanId) -> bool {
+        clans::is_clan_official_game_group(self, clan_id)
+    }
+
+    /// Fetches the owner and officers of a Steam group.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#RequestClanOfficerList>
+    pub fn request_clan_officer_list(
+        &self,
+        clan_id: ClanId,
+    ) -> BoxFuture<'_, Result<clans::ClanOfficers, clans::RequestClanOfficerListError>> {
+        clans::request_clan_officer_list(self, clan_id).boxed()
+    }
+
+    /// Returns the activity counts of a Steam group, which are available for the current user's
+    /// groups and for groups downloaded with
+    /// [`download_clan_activity_counts`](Self::download_clan_activity_counts).
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GetClanActivityCounts>
+    pub fn clan_activity_counts(&self, clan_id: ClanId) -> Option<clans::ClanActivityCounts> {
+        clans::clan_activity_counts(self, clan_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#DownloadClanActivityCounts>
+    pub fn download_clan_activity_counts(
+        &self,
+        clan_ids: &[ClanId],
+    ) -> BoxFuture<'_, Result<(), clans::DownloadClanActivityCountsError>> {
+        clans::download_clan_activity_counts(self, clan_ids.to_vec()).boxed()
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#ActivateGameOverlay>
+    pub fn activate_game_overlay(&self, dialog: overlay::GameOverlayDialog) {
+        overlay::activate_game_overlay(self, dialog)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#ActivateGameOverlayToUser>
+    pub fn activate_overlay_to_user(&self, dialog: overlay::UserOverlayDialog, steam_id: SteamId) {
+        overlay::activate_overlay_to_user(self, dialog, steam_id)
+    }
+
+    /// Opens a web page in the Steam overlay's browser.
+    ///
+    /// See <https://partner.steamgames.com/doc/api/ISteamFriends#ActivateGameOverlayToWebPage>.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `url` contains nul bytes.
+    pub fn activate_overlay_to_web_page(&self, url: &str, mode: overlay::WebPageMode) {
+        overlay::activate_overlay_to_web_page(self, url, mode)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#ActivateGameOverlayToStore>
+    pub fn activate_overlay_to_store(&self, app_id: AppId, flag: overlay::OverlayToStoreFlag) {
+        overlay::activate_overlay_to_store(self, app_id, flag)
+    }
+
+    /// Opens the Steam overlay's dialog for inviting friends to a lobby.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#ActivateGameOverlayInviteDialog>
+    pub fn activate_invite_dialog(&self, lobby: SteamId) {
+        overlay::activate_invite_dialog(self, lobby)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUtils#IsOverlayEnabled>
+    pub fn is_overlay_enabled(&self) -> bool {
+        overlay::is_overlay_enabled(self)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUtils#SetOverlayNotificationPosition>
+    pub fn set_overlay_notification_position(&self, position: overlay::NotificationPosition) {
+        overlay::set_overlay_notification_position(self, position)
+    }
+
+    /// Sets the inset of the overlay's notification popups from the corner set with
+    /// [`set_overlay_notification_position`](Self::set_overlay_notification_position), in pixels.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUtils#SetOverlayNotificationInset>
+    pub fn set_overlay_notification_inset(&self, horizontal: i32, vertical: i32) {
+        overlay::set_overlay_notification_inset(self, horizontal, vertical)
+    }
+
+    /// Whether the current user owns the current app.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#BIsSubscribed>
+    pub fn is_subscribed(&self) -> bool {
+        apps::is_subscribed(self)
+    }
+
+    /// Whether the current user owns an app, such as another game or a DLC.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#BIsSubscribedApp>
+    pub fn is_subscribed_app(&self, app_id: AppId) -> bool {
+        apps::is_subscribed_app(self, app_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#BIsDlcInstalled>
+    pub fn is_dlc_installed(&self, app_id: AppId) -> bool {
+        apps::is_dlc_installed(self, app_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#BIsVACBanned>
+    pub fn is_vac_banned(&self) -> bool {
+        apps::is_vac_banned(self)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#BIsLowViolence>
+    pub fn is_low_violence(&self) -> bool {
+        apps::is_low_violence(self)
+    }
+
+    /// Returns the language the user has chosen for the current app, such as `"english"`.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetCurrentGameLanguage>
+    pub fn current_game_language(&self) -> String {
+        apps::current_game_language(self)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetAvailableGameLanguages>
+    pub fn available_game_languages(&self) -> Vec<String> {
+        apps::available_game_languages(self)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetAppBuildId>
+    pub fn app_build_id(&self) -> i32 {
+        apps::app_build_id(self)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetAppInstallDir>
+    ///
+    /// Returns `None` if the app is not installed.
+    pub fn app_install_dir(&self, app_id: AppId) -> Option<PathBuf> {
+        apps::app_install_dir(self, app_id)
+    }
+
+    /// Returns the owner of the current app, which differs from the current user if the app is
+    /// borrowed through Steam Family Sharing.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetAppOwner>
+    pub fn app_owner(&self) -> SteamId {
+        apps::app_owner(self)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetEarliestPurchaseUnixTime>
+    ///
+    /// Returns `None` if the current user doesn't own the app.
+    pub fn earliest_purchase_time(&self, app_id: AppId) -> Option<DateTime<Utc>> {
+        apps::earliest_purchase_time(self, app_id)
+    }
+
+    /// Returns the DLC of the current app.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#BGetDLCDataByIndex>
+    pub fn dlc(&self) -> Vec<apps::DlcInfo> {
+        apps::dlc(self)
+    }
+
+    /// Starts installing an optional DLC. Use [`on_dlc_installed`](Self::on_dlc_installed) to be
+    /// notified once it has been installed.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#InstallDLC>
+    pub fn install_dlc(&self, app_id: AppId) {
+        apps::install_dlc(self, app_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#UninstallDLC>
+    pub fn uninstall_dlc(&self, app_id: AppId) {
+        apps::uninstall_dlc(self, app_id)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetDlcDownloadProgress>
+    ///
+    /// Returns `None` if the DLC is not being downloaded.
+    pub fn dlc_download_progress(&self, app_id: AppId) -> Option<apps::DlcDownloadProgress> {
+        apps::dlc_download_progress(self, app_id)
+    }
+
+    /// Returns the name of the beta branch the current app is running from, or `None` if it's
+    /// running from the default branch.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetCurrentBetaName>
+    pub fn current_beta_name(&self) -> Option<String> {
+        apps::current_beta_name(self)
+    }
+
+    /// Returns the value of a query parameter of the `steam://run/<appid>//?<params>` URL the app
+    /// was launched with, or `None` if the parameter isn't set.
+    ///
+    /// The parameters change when the app is launched again through a URL while it's running; see
+    /// [`on_new_url_launch_parameters`](Self::on_new_url_launch_parameters). See
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetLaunchQueryParam>.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` contains nul bytes.
+    pub fn launch_query_param(&self, key: &str) -> Option<String> {
+        apps::launch_query_param(self, key)
+    }
+
+    /// Returns the command line of the `steam://run/<appid>//<command line>` URL the app was
+    /// launched with.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetLaunchCommandLine>
+    pub fn launch_command_line(&self) -> String {
+        apps::launch_command_line(self)
+    }
+
+    /// Asks Steam to verify the current app's files the next time it runs, for example after
+    /// detecting a damaged install. Returns whether the request succeeded.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#MarkContentCorrupt>
+    pub fn mark_content_corrupt(&self, missing_files_only: bool) -> bool {
+        apps::mark_content_corrupt(self, missing_files_only)
+    }
+
+    /// Returns the installed depots of an app, in mount order.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetInstalledDepots>
+    pub fn installed_depots(&self, app_id: AppId) -> Vec<apps::DepotId> {
+        apps::installed_depots(self, app_id)
+    }
+
+    /// Fetches the size and hash Steam's manifest lists for one of the current app's files, so
+    /// that the file can be checked for damage. `file_name` is relative to the app's install
+    /// directory.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#GetFileDetails>
+    pub fn file_details(
+        &self,
+        file_name: impl Into<Vec<u8>>,
+    ) -> BoxFuture<'_, Result<apps::FileDetails, apps::FileDetailsError>> {
+        apps::file_details(self, file_name.into()).boxed()
+    }
+
     /// <https://partner.steamgames.com/doc/api/ISteamUtils#GetAppID>
     pub fn app_id(&self) -> AppId {
         unsafe { sys::SteamAPI_ISteamUtils_GetAppID(*self.0.utils).into() }
@@ -192,11 +842,97 @@ impl Client {
         callbacks::register_to_receive_callback(&self.0.callback_dispatchers.persona_state_change)
     }
 
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#ItemInstalled_t>
+    pub fn on_item_installed(&self) -> impl Stream<Item = callbacks::ItemInstalled> + Send {
+        callbacks::register_to_receive_callback(&self.0.callback_dispatchers.item_installed)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#DownloadItemResult_t>
+    pub fn on_download_item_result(
+        &self,
+    ) -> impl Stream<Item = callbacks::DownloadItemResult> + Send {
+        callbacks::register_to_receive_callback(&self.0.callback_dispatchers.download_item_result)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#AvatarImageLoaded_t>
+    pub fn on_avatar_image_loaded(
+        &self,
+    ) -> impl Stream<Item = callbacks::AvatarImageLoaded> + Send {
+        callbacks::register_to_receive_callback(&self.0.callback_dispatchers.avatar_image_loaded)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#FriendRichPresenceUpdate_t>
+    pub fn on_friend_rich_presence_update(
+        &self,
+    ) -> impl Stream<Item = callbacks::FriendRichPresenceUpdate> + Send {
+        callbacks::register_to_receive_callback(
+            &self.0.callback_dispatchers.friend_rich_presence_update,
+        )
+    }
+
+    /// Yields when the user accepts an invite or joins a friend's game through the Steam friends
+    /// list.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GameRichPresenceJoinRequested_t>
+    pub fn on_game_rich_presence_join_requested(
+        &self,
+    ) -> impl Stream<Item = callbacks::GameRichPresenceJoinRequested> + Send {
+        callbacks::register_to_receive_callback(
+            &self
+                .0
+                .callback_dispatchers
+                .game_rich_presence_join_requested,
+        )
+    }
+
+    /// Yields when the user tries to join a lobby through the Steam friends list or an invite.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GameLobbyJoinRequested_t>
+    pub fn on_game_lobby_join_requested(
+        &self,
+    ) -> impl Stream<Item = callbacks::GameLobbyJoinRequested> + Send {
+        callbacks::register_to_receive_callback(
+            &self.0.callback_dispatchers.game_lobby_join_requested,
+        )
+    }
+
+    /// Yields whenever the Steam overlay is opened or closed, which is a good time to pause the
+    /// game.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#GameOverlayActivated_t>
+    pub fn on_game_overlay_activated(
+        &self,
+    ) -> impl Stream<Item = callbacks::GameOverlayActivated> + Send {
+        callbacks::register_to_receive_callback(&self.0.callback_dispatchers.game_overlay_activated)
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#DlcInstalled_t>
+    pub fn on_dlc_installed(&self) -> impl Stream<Item = callbacks::DlcInstalled> + Send {
+        callbacks::register_to_receive_callback(&self.0.callback_dispatchers.dlc_installed)
+    }
+
+    /// Yields when the app is launched again through a `steam://run` URL while it's running, after
+    /// which [`launch_query_param`](Self::launch_query_param) and
+    /// [`launch_command_line`](Self::launch_command_line) return the new parameters.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamApps#NewUrlLaunchParameters_t>
+    pub fn on_new_url_launch_parameters(&self) -> impl Stream<Item = ()> + Send {
+        callbacks::register_to_receive_callback(
+            &self.0.callback_dispatchers.new_url_launch_parameters,
+        )
+    }
+
     /// <https://partner.steamgames.com/doc/api/ISteamUtils#SteamShutdown_t>
     pub fn on_steam_shutdown(&self) -> impl Stream<Item = ()> + Send {
         callbacks::register_to_receive_callback(&self.0.callback_dispatchers.steam_shutdown)
     }
 
+    /// Returns a future that completes after `duration` has elapsed. The timer is driven by the
+    /// worker thread, so its resolution is roughly that of the worker thread's polling interval.
+    pub(crate) fn delay(&self, duration: Duration) -> TimerFuture<'_> {
+        self.0.timer.delay(duration)
+    }
+
     async unsafe fn register_for_call_result<CallResult: Copy>(
         &self,
         handle: sys::SteamAPICall_t,
@@ -308,7 +1044,7 @@ fn start_worker_thread(client: Client) {
                     } else {
                         // It's a callback
 
-                        callbacks::dispatch_callbacks(&client.0.callback_dispatchers, callback);
+                        callbacks::dispatch_callbacks(&client, callback);
                     }
 
                     sys::SteamAPI_ManualDispatch_FreeLastCallback(steam_pipe);
@@ -331,6 +1067,7 @@ fn start_worker_thread(client: Client) {
                     break;
                 }
 
+                client.0.timer.check_expirations();
                 thread::sleep(Duration::from_millis(1));
             }
         }
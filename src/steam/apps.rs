@@ -0,0 +1,315 @@
+use crate::string_ext::FromUtf8NulTruncating;
+use crate::{AppId, Client, SteamId, SteamResult};
+use chrono::{DateTime, TimeZone, Utc};
+use snafu::{ensure, ResultExt};
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString, NulError};
+use std::os::raw::c_char;
+use std::path::PathBuf;
+use steamworks_sys as sys;
+
+/// <https://partner.steamgames.com/doc/api/ISteamApps#BGetDLCDataByIndex>
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct DlcInfo {
+    pub app_id: AppId,
+    /// Whether the DLC is currently available on the Steam store.
+    pub available: bool,
+    pub name: String,
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamApps#GetDlcDownloadProgress>
+#[derive(Debug, Copy, Clone, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct DlcDownloadProgress {
+    pub bytes_downloaded: u64,
+    pub bytes_total: u64,
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct DepotId(pub u32);
+
+impl From<u32> for DepotId {
+    fn from(x: u32) -> DepotId {
+        DepotId(x)
+    }
+}
+
+impl From<DepotId> for u32 {
+    fn from(x: DepotId) -> u32 {
+        x.0
+    }
+}
+
+/// The details of one of the current app's files, according to Steam's manifest.
+///
+/// <https://partner.steamgames.com/doc/api/ISteamApps#FileDetailsResult_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct FileDetails {
+    /// The original file size in bytes.
+    pub size: u64,
+    /// The original SHA-1 hash of the file.
+    pub sha1: [u8; 20],
+    pub flags: u32,
+}
+
+#[derive(Debug, snafu::Snafu)]
+#[snafu(module)]
+pub enum FileDetailsError {
+    /// The file name contains nul byte(s)
+    #[snafu(display("The file name contains nul byte(s): {}", source))]
+    Nul { source: NulError },
+
+    /// `GetFileDetails()` failed
+    #[snafu(display("GetFileDetails() failed: {}", steam_result))]
+    GetFileDetails { steam_result: SteamResult },
+}
+
+pub(crate) fn is_subscribed(client: &Client) -> bool {
+    unsafe { sys::SteamAPI_ISteamApps_BIsSubscribed(*client.0.apps) }
+}
+
+pub(crate) fn is_subscribed_app(client: &Client, app_id: AppId) -> bool {
+    unsafe { sys::SteamAPI_ISteamApps_BIsSubscribedApp(*client.0.apps, app_id.into()) }
+}
+
+pub(crate) fn is_dlc_installed(client: &Client, app_id: AppId) -> bool {
+    unsafe { sys::SteamAPI_ISteamApps_BIsDlcInstalled(*client.0.apps, app_id.into()) }
+}
+
+pub(crate) fn is_vac_banned(client: &Client) -> bool {
+    unsafe { sys::SteamAPI_ISteamApps_BIsVACBanned(*client.0.apps) }
+}
+
+pub(crate) fn is_low_violence(client: &Client) -> bool {
+    unsafe { sys::SteamAPI_ISteamApps_BIsLowViolence(*client.0.apps) }
+}
+
+pub(crate) fn current_game_language(client: &Client) -> String {
+    unsafe {
+        let language = sys::SteamAPI_ISteamApps_GetCurrentGameLanguage(*client.0.apps);
+
+        CStr::from_ptr(language).to_string_lossy().into_owned()
+    }
+}
+
+pub(crate) fn available_game_languages(client: &Client) -> Vec<String> {
+    let languages = unsafe {
+        let languages = sys::SteamAPI_ISteamApps_GetAvailableGameLanguages(*client.0.apps);
+
+        CStr::from_ptr(languages).to_string_lossy().into_owned()
+    };
+
+    split_language_list(&languages)
+}
+
+/// Splits a comma-separated list of languages, as returned by `GetAvailableGameLanguages()`.
+fn split_language_list(languages: &str) -> Vec<String> {
+    languages
+        .split(',')
+        .map(str::trim)
+        .filter(|language| !language.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+pub(crate) fn app_build_id(client: &Client) -> i32 {
+    unsafe { sys::SteamAPI_ISteamApps_GetAppBuildId(*client.0.apps) }
+}
+
+pub(crate) fn app_install_dir(client: &Client, app_id: AppId) -> Option<PathBuf> {
+    let mut folder = vec![0_u8; 4096];
+    let length = unsafe {
+        sys::SteamAPI_ISteamApps_GetAppInstallDir(
+            *client.0.apps,
+            app_id.into(),
+            folder.as_mut_ptr() as *mut c_char,
+            u32::try_from(folder.len()).unwrap(),
+        )
+    };
+    if length == 0 {
+        return None;
+    }
+
+    let folder = String::from_utf8_nul_truncating(folder)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+
+    Some(folder.into())
+}
+
+pub(crate) fn app_owner(client: &Client) -> SteamId {
+    unsafe { sys::SteamAPI_ISteamApps_GetAppOwner(*client.0.apps).into() }
+}
+
+pub(crate) fn earliest_purchase_time(client: &Client, app_id: AppId) -> Option<DateTime<Utc>> {
+    let time = unsafe {
+        sys::SteamAPI_ISteamApps_GetEarliestPurchaseUnixTime(*client.0.apps, app_id.into())
+    };
+    if time == 0 {
+        return None;
+    }
+
+    Utc.timestamp_opt(i64::from(time), 0).single()
+}
+
+pub(crate) fn dlc(client: &Client) -> Vec<DlcInfo> {
+    let count = unsafe { sys::SteamAPI_ISteamApps_GetDLCCount(*client.0.apps) };
+
+    (0..count)
+        .filter_map(|i| {
+            let mut app_id = 0;
+            let mut available = false;
+            let mut name = vec![0_u8; 256];
+            let success = unsafe {
+                sys::SteamAPI_ISteamApps_BGetDLCDataByIndex(
+                    *client.0.apps,
+                    i,
+                    &mut app_id,
+                    &mut available,
+                    name.as_mut_ptr() as *mut c_char,
+                    i32::try_from(name.len()).unwrap(),
+                )
+            };
+            if !success {
+                return None;
+            }
+
+            Some(DlcInfo {
+                app_id: app_id.into(),
+                available,
+                name: String::from_utf8_nul_truncating(name)
+                    .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn install_dlc(client: &Client, app_id: AppId) {
+    unsafe { sys::SteamAPI_ISteamApps_InstallDLC(*client.0.apps, app_id.into()) }
+}
+
+pub(crate) fn uninstall_dlc(client: &Client, app_id: AppId) {
+    unsafe { sys::SteamAPI_ISteamApps_UninstallDLC(*client.0.apps, app_id.into()) }
+}
+
+pub(crate) fn dlc_download_progress(client: &Client, app_id: AppId) -> Option<DlcDownloadProgress> {
+    let mut bytes_downloaded = 0;
+    let mut bytes_total = 0;
+    let downloading = unsafe {
+        sys::SteamAPI_ISteamApps_GetDlcDownloadProgress(
+            *client.0.apps,
+            app_id.into(),
+            &mut bytes_downloaded,
+            &mut bytes_total,
+        )
+    };
+
+    if downloading {
+        Some(DlcDownloadProgress {
+            bytes_downloaded,
+            bytes_total,
+        })
+    } else {
+        None
+    }
+}
+
+pub(crate) fn current_beta_name(client: &Client) -> Option<String> {
+    let mut name = vec![0_u8; 256];
+    let on_beta = unsafe {
+        sys::SteamAPI_ISteamApps_GetCurrentBetaName(
+            *client.0.apps,
+            name.as_mut_ptr() as *mut c_char,
+            i32::try_from(name.len()).unwrap(),
+        )
+    };
+    if !on_beta {
+        return None;
+    }
+
+    Some(
+        String::from_utf8_nul_truncating(name)
+            .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()),
+    )
+}
+
+pub(crate) fn launch_query_param(client: &Client, key: &str) -> Option<String> {
+    let key = CString::new(key).expect("Key contains nul byte(s)");
+    let value = unsafe {
+        let value = sys::SteamAPI_ISteamApps_GetLaunchQueryParam(*client.0.apps, key.as_ptr());
+
+        CStr::from_ptr(value).to_string_lossy().into_owned()
+    };
+
+    Some(value).filter(|value| !value.is_empty())
+}
+
+pub(crate) fn launch_command_line(client: &Client) -> String {
+    let mut command_line = vec![0_u8; 4096];
+    let length = unsafe {
+        sys::SteamAPI_ISteamApps_GetLaunchCommandLine(
+            *client.0.apps,
+            command_line.as_mut_ptr() as *mut c_char,
+            i32::try_from(command_line.len()).unwrap(),
+        )
+    };
+    command_line.truncate(usize::try_from(length).unwrap_or(0));
+
+    String::from_utf8_nul_truncating(command_line)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+pub(crate) fn mark_content_corrupt(client: &Client, missing_files_only: bool) -> bool {
+    unsafe { sys::SteamAPI_ISteamApps_MarkContentCorrupt(*client.0.apps, missing_files_only) }
+}
+
+pub(crate) fn installed_depots(client: &Client, app_id: AppId) -> Vec<DepotId> {
+    let mut depots = vec![0; 256];
+    let count = unsafe {
+        sys::SteamAPI_ISteamApps_GetInstalledDepots(
+            *client.0.apps,
+            app_id.into(),
+            depots.as_mut_ptr(),
+            u32::try_from(depots.len()).unwrap(),
+        )
+    };
+    depots.truncate(count as usize);
+
+    depots.into_iter().map(DepotId).collect()
+}
+
+pub(crate) async fn file_details(
+    client: &Client,
+    file_name: Vec<u8>,
+) -> Result<FileDetails, FileDetailsError> {
+    let file_name = CString::new(file_name).context(file_details_error::NulSnafu)?;
+    let response: sys::FileDetailsResult_t = unsafe {
+        let handle = sys::SteamAPI_ISteamApps_GetFileDetails(*client.0.apps, file_name.as_ptr());
+
+        client.register_for_call_result(handle).await
+    };
+
+    let steam_result = SteamResult::from_inner(response.m_eResult);
+    ensure!(
+        steam_result == SteamResult::OK,
+        file_details_error::GetFileDetailsSnafu { steam_result }
+    );
+
+    Ok(FileDetails {
+        size: response.m_ulFileSize,
+        sha1: response.m_FileSHA,
+        flags: response.m_unFlags,
+    })
+}
+
+#[test]
+fn test_split_language_list() {
+    assert_eq!(
+        split_language_list("english,french, german"),
+        vec!["english", "french", "german"]
+    );
+    assert_eq!(split_language_list("english"), vec!["english"]);
+    assert!(split_language_list("").is_empty());
+    assert_eq!(
+        split_language_list("english,,french,"),
+        vec!["english", "french"]
+    );
+}
@@ -0,0 +1,167 @@
+use crate::{ClanId, Client, SteamId};
+use snafu::ensure;
+use std::convert::TryFrom;
+use std::ffi::CStr;
+use steamworks_sys as sys;
+
+/// The officers of a Steam group, as returned by [`Client::request_clan_officer_list`].
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct ClanOfficers {
+    pub owner: SteamId,
+    /// The group's officers, not including the owner.
+    pub officers: Vec<SteamId>,
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#GetClanActivityCounts>
+#[derive(Debug, Copy, Clone, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ClanActivityCounts {
+    pub online: u32,
+    pub in_game: u32,
+    pub chatting: u32,
+}
+
+#[derive(Debug, snafu::Snafu)]
+#[snafu(module)]
+pub enum RequestClanOfficerListError {
+    /// `RequestClanOfficerList()` failed
+    #[snafu(display("RequestClanOfficerList() failed"))]
+    RequestClanOfficerList,
+}
+
+#[derive(Debug, snafu::Snafu)]
+#[snafu(module)]
+pub enum DownloadClanActivityCountsError {
+    /// `DownloadClanActivityCounts()` failed
+    #[snafu(display("DownloadClanActivityCounts() failed"))]
+    DownloadClanActivityCounts,
+}
+
+pub(crate) fn clans(client: &Client) -> Vec<ClanId> {
+    unsafe {
+        let count = sys::SteamAPI_ISteamFriends_GetClanCount(*client.0.friends);
+
+        (0..count)
+            .filter_map(|i| {
+                let steam_id = sys::SteamAPI_ISteamFriends_GetClanByIndex(*client.0.friends, i);
+
+                ClanId::from_steam_id(steam_id.into())
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn clan_name(client: &Client, clan_id: ClanId) -> String {
+    unsafe {
+        let name = sys::SteamAPI_ISteamFriends_GetClanName(*client.0.friends, clan_id.as_u64());
+
+        CStr::from_ptr(name).to_string_lossy().into_owned()
+    }
+}
+
+pub(crate) fn clan_tag(client: &Client, clan_id: ClanId) -> String {
+    unsafe {
+        let tag = sys::SteamAPI_ISteamFriends_GetClanTag(*client.0.friends, clan_id.as_u64());
+
+        CStr::from_ptr(tag).to_string_lossy().into_owned()
+    }
+}
+
+pub(crate) fn is_clan_public(client: &Client, clan_id: ClanId) -> bool {
+    unsafe { sys::SteamAPI_ISteamFriends_IsClanPublic(*client.0.friends, clan_id.as_u64()) }
+}
+
+pub(crate) fn is_clan_official_game_group(client: &Client, clan_id: ClanId) -> bool {
+    unsafe {
+        sys::SteamAPI_ISteamFriends_IsClanOfficialGameGroup(*client.0.friends, clan_id.as_u64())
+    }
+}
+
+pub(crate) async fn request_clan_officer_list(
+    client: &Client,
+    clan_id: ClanId,
+) -> Result<ClanOfficers, RequestClanOfficerListError> {
+    let response: sys::ClanOfficerListResponse_t = unsafe {
+        let handle =
+            sys::SteamAPI_ISteamFriends_RequestClanOfficerList(*client.0.friends, clan_id.as_u64());
+
+        client.register_for_call_result(handle).await
+    };
+    ensure!(
+        response.m_bSuccess != 0,
+        request_clan_officer_list_error::RequestClanOfficerListSnafu
+    );
+
+    unsafe {
+        let friends = *client.0.friends;
+        let owner: SteamId =
+            sys::SteamAPI_ISteamFriends_GetClanOwner(friends, clan_id.as_u64()).into();
+        let count = sys::SteamAPI_ISteamFriends_GetClanOfficerCount(friends, clan_id.as_u64());
+        // Steam lists the owner as an officer too
+        let officers = (0..count)
+            .map(|i| {
+                SteamId::from(sys::SteamAPI_ISteamFriends_GetClanOfficerByIndex(
+                    friends,
+                    clan_id.as_u64(),
+                    i,
+                ))
+            })
+            .filter(|&officer| officer != owner)
+            .collect();
+
+        Ok(ClanOfficers { owner, officers })
+    }
+}
+
+pub(crate) fn clan_activity_counts(client: &Client, clan_id: ClanId) -> Option<ClanActivityCounts> {
+    let mut online = 0;
+    let mut in_game = 0;
+    let mut chatting = 0;
+    let success = unsafe {
+        sys::SteamAPI_ISteamFriends_GetClanActivityCounts(
+            *client.0.friends,
+            clan_id.as_u64(),
+            &mut online,
+            &mut in_game,
+            &mut chatting,
+        )
+    };
+
+    if success {
+        Some(ClanActivityCounts {
+            online: online as u32,
+            in_game: in_game as u32,
+            chatting: chatting as u32,
+        })
+    } else {
+        None
+    }
+}
+
+pub(crate) async fn download_clan_activity_counts(
+    client: &Client,
+    clan_ids: Vec<ClanId>,
+) -> Result<(), DownloadClanActivityCountsError> {
+    let mut clan_ids: Vec<sys::CSteamID> = clan_ids
+        .into_iter()
+        .map(|clan_id| sys::CSteamID {
+            m_steamid: sys::CSteamID_SteamID_t {
+                m_unAll64Bits: clan_id.as_u64(),
+            },
+        })
+        .collect();
+    let response: sys::DownloadClanActivityCountsResult_t = unsafe {
+        let handle = sys::SteamAPI_ISteamFriends_DownloadClanActivityCounts(
+            *client.0.friends,
+            clan_ids.as_mut_ptr(),
+            i32::try_from(clan_ids.len()).unwrap(),
+        );
+
+        client.register_for_call_result(handle).await
+    };
+    ensure!(
+        response.m_bSuccess,
+        download_clan_activity_counts_error::DownloadClanActivityCountsSnafu
+    );
+
+    Ok(())
+}
@@ -1,12 +1,13 @@
 use crate::callbacks::PersonaStateChangeFlags;
+use crate::friends::{self, AvatarImage, AvatarSize};
 use crate::Client;
 use enum_primitive_derive::Primitive;
-use futures::{Future, StreamExt};
+use futures::{future, pin_mut, stream, Future, StreamExt};
 use num_traits::FromPrimitive;
 use std::cmp::Ordering;
-use std::ffi::CStr;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
+use std::time::Duration;
 use steamworks_sys as sys;
 use steamworks_sys::CSteamID;
 
@@ -31,6 +32,22 @@ impl From<AppId> for u32 {
     }
 }
 
+/// The account ID portion of a [`SteamId`].
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct AccountId(pub u32);
+
+impl From<u32> for AccountId {
+    fn from(x: u32) -> AccountId {
+        AccountId(x)
+    }
+}
+
+impl From<AccountId> for u32 {
+    fn from(x: AccountId) -> u32 {
+        x.0
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct SteamId(pub(crate) u64);
 
@@ -39,31 +56,61 @@ impl SteamId {
         id.into()
     }
 
-    pub fn persona_name(self, client: &Client) -> impl Future<Output = String> + Send + '_ {
-        let mut persona_state_changes = client.on_persona_state_changed();
-        let request_in_progress = unsafe {
-            sys::SteamAPI_ISteamFriends_RequestUserInformation(*client.0.friends, self.0, true)
+    /// Fetches the user's persona name. Invalid UTF-8 in the name is replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// Names are shared with the cache of [`Client::persona_names`].
+    pub async fn persona_name(self, client: &Client) -> String {
+        match friends::persona_names(client, vec![self], None).await.pop() {
+            Some(Some(name)) => name,
+            // Steam shut down before the user's information arrived
+            _ => friends::persona_name(client, self),
+        }
+    }
+
+    /// Fetches the user's avatar, loading it first if necessary.
+    ///
+    /// Resolves to `None` if the user has no avatar, if the avatar image couldn't be read, or if
+    /// it didn't finish loading within `timeout`.
+    pub fn avatar(
+        self,
+        client: &Client,
+        size: AvatarSize,
+        timeout: Duration,
+    ) -> impl Future<Output = Option<AvatarImage>> + Send + '_ {
+        let avatars_loaded = client
+            .on_avatar_image_loaded()
+            .filter(move |loaded| future::ready(loaded.steam_id == self))
+            .map(|_| ());
+        let avatar_changes = client
+            .on_persona_state_changed()
+            .filter(move |change| {
+                future::ready(
+                    change.steam_id == self
+                        && change
+                            .change_flags
+                            .contains(PersonaStateChangeFlags::AVATAR),
+                )
+            })
+            .map(|_| ());
+        let events =
+            stream::select(avatars_loaded, avatar_changes).take_until(client.delay(timeout));
+        let mut request_in_progress = unsafe {
+            sys::SteamAPI_ISteamFriends_RequestUserInformation(*client.0.friends, self.0, false)
         };
         async move {
-            if request_in_progress {
-                loop {
-                    let change = persona_state_changes.next().await.unwrap();
-                    if change.steam_id == self
-                        && change.change_flags.contains(PersonaStateChangeFlags::NAME)
-                    {
-                        break;
-                    }
+            pin_mut!(events);
+            loop {
+                match friends::avatar_handle(client, self, size) {
+                    -1 => {}
+                    0 if !request_in_progress => return None,
+                    0 => {}
+                    handle => return friends::avatar_image(client, handle),
                 }
-            }
-
-            unsafe {
-                let name =
-                    sys::SteamAPI_ISteamFriends_GetFriendPersonaName(*client.0.friends, self.0);
 
-                CStr::from_ptr(name)
-                    .to_owned()
-                    .into_string()
-                    .expect("persona name contained invalid UTF-8")
+                // The stream only ends once the timeout has elapsed
+                events.next().await?;
+                request_in_progress = false;
             }
         }
     }
@@ -71,6 +118,69 @@ impl SteamId {
     pub fn as_u64(self) -> u64 {
         self.0
     }
+
+    /// Returns the account ID, which is stored in the lower 32 bits of the Steam ID.
+    pub fn account_id(self) -> AccountId {
+        AccountId(self.0 as u32)
+    }
+
+    /// Returns the account type, which is stored in bits 52 to 55 of the Steam ID.
+    pub fn account_type(self) -> AccountType {
+        AccountType::from_inner(((self.0 >> 52) & 0xF) as sys::EAccountType)
+    }
+}
+
+/// <https://partner.steamgames.com/doc/api/steam_api#EAccountType>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Primitive)]
+#[repr(i32)]
+pub enum AccountType {
+    Invalid = sys::EAccountType_k_EAccountTypeInvalid as i32,
+    Individual = sys::EAccountType_k_EAccountTypeIndividual as i32,
+    Multiseat = sys::EAccountType_k_EAccountTypeMultiseat as i32,
+    GameServer = sys::EAccountType_k_EAccountTypeGameServer as i32,
+    AnonGameServer = sys::EAccountType_k_EAccountTypeAnonGameServer as i32,
+    Pending = sys::EAccountType_k_EAccountTypePending as i32,
+    ContentServer = sys::EAccountType_k_EAccountTypeContentServer as i32,
+    Clan = sys::EAccountType_k_EAccountTypeClan as i32,
+    Chat = sys::EAccountType_k_EAccountTypeChat as i32,
+    ConsoleUser = sys::EAccountType_k_EAccountTypeConsoleUser as i32,
+    AnonUser = sys::EAccountType_k_EAccountTypeAnonUser as i32,
+}
+
+impl AccountType {
+    pub(crate) fn from_inner(inner: sys::EAccountType) -> Self {
+        // Steam IDs can hold account types this SDK doesn't know about
+        AccountType::from_i32(inner as i32).unwrap_or(AccountType::Invalid)
+    }
+}
+
+/// The Steam ID of a Steam group, also known as a clan.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ClanId(SteamId);
+
+impl ClanId {
+    /// Returns `None` if the Steam ID's account type isn't [`AccountType::Clan`].
+    pub fn from_steam_id(steam_id: SteamId) -> Option<Self> {
+        if steam_id.account_type() == AccountType::Clan {
+            Some(ClanId(steam_id))
+        } else {
+            None
+        }
+    }
+
+    pub fn steam_id(self) -> SteamId {
+        self.0
+    }
+
+    pub fn as_u64(self) -> u64 {
+        self.0.as_u64()
+    }
+}
+
+impl From<ClanId> for SteamId {
+    fn from(clan_id: ClanId) -> Self {
+        clan_id.0
+    }
 }
 
 impl From<u64> for SteamId {
@@ -247,6 +357,13 @@ impl SteamResult {
         SteamResult::from_i32(inner as i32)
             .unwrap_or_else(|| panic!("Unknown EResult variant: {}", inner))
     }
+
+    /// Like [`from_inner`](Self::from_inner), but maps results unknown to this crate to `Fail`
+    /// instead of panicking. Use this on the worker thread, where a panic would stop all callback
+    /// dispatch, and for results that are reported per item rather than per call.
+    pub(crate) fn from_inner_or_fail(inner: sys::EResult) -> Self {
+        SteamResult::from_i32(inner as i32).unwrap_or(SteamResult::Fail)
+    }
 }
 
 impl Display for SteamResult {
@@ -371,3 +488,31 @@ impl Display for SteamResult {
         write!(f, "{}", error_string)
     }
 }
+
+#[test]
+fn test_steam_id_account_type() {
+    assert_eq!(
+        SteamId::new(76561197960287930).account_type(),
+        AccountType::Individual
+    );
+    assert_eq!(
+        SteamId::new(103582791429521408).account_type(),
+        AccountType::Clan
+    );
+    assert_eq!(SteamId::new(0).account_type(), AccountType::Invalid);
+    // Account types unknown to the SDK are mapped to `Invalid`
+    assert_eq!(
+        SteamId::new(0x01F0_0000_0000_0000).account_type(),
+        AccountType::Invalid
+    );
+}
+
+#[test]
+fn test_clan_id_from_steam_id() {
+    let clan = SteamId::new(103582791429521408);
+    assert_eq!(
+        ClanId::from_steam_id(clan).map(ClanId::as_u64),
+        Some(clan.as_u64())
+    );
+    assert_eq!(ClanId::from_steam_id(SteamId::new(76561197960287930)), None);
+}
@@ -0,0 +1,566 @@
+#![allow(clippy::unnecessary_cast)]
+
+use crate::callbacks::{PersonaStateChange, PersonaStateChangeFlags};
+use crate::{AppId, Client, SteamId};
+use bitflags::bitflags;
+use chrono::{DateTime, TimeZone, Utc};
+use enum_primitive_derive::Primitive;
+use fnv::{FnvHashMap, FnvHashSet};
+use futures::{future, pin_mut, FutureExt, StreamExt};
+use num_traits::FromPrimitive;
+use parking_lot::Mutex;
+use snafu::{ensure, ResultExt};
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString, NulError};
+use std::fmt::{self, Debug, Formatter};
+use std::mem::MaybeUninit;
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::time::Duration;
+use steamworks_sys as sys;
+
+bitflags! {
+    /// Selects which users are returned by [`Client::friends`].
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamFriends#EFriendFlags>
+    #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct FriendFlags: u32 {
+        const BLOCKED = sys::EFriendFlags_k_EFriendFlagBlocked as u32;
+        const FRIENDSHIP_REQUESTED = sys::EFriendFlags_k_EFriendFlagFriendshipRequested as u32;
+        /// The user's regular friends.
+        const IMMEDIATE = sys::EFriendFlags_k_EFriendFlagImmediate as u32;
+        const CLAN_MEMBER = sys::EFriendFlags_k_EFriendFlagClanMember as u32;
+        const ON_GAME_SERVER = sys::EFriendFlags_k_EFriendFlagOnGameServer as u32;
+        const REQUESTING_FRIENDSHIP = sys::EFriendFlags_k_EFriendFlagRequestingFriendship as u32;
+        const REQUESTING_INFO = sys::EFriendFlags_k_EFriendFlagRequestingInfo as u32;
+        const IGNORED = sys::EFriendFlags_k_EFriendFlagIgnored as u32;
+        const IGNORED_FRIEND = sys::EFriendFlags_k_EFriendFlagIgnoredFriend as u32;
+        const CHAT_MEMBER = sys::EFriendFlags_k_EFriendFlagChatMember as u32;
+    }
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#EFriendRelationship>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Primitive)]
+#[repr(i32)]
+pub enum FriendRelationship {
+    None = sys::EFriendRelationship_k_EFriendRelationshipNone as i32,
+    Blocked = sys::EFriendRelationship_k_EFriendRelationshipBlocked as i32,
+    RequestRecipient = sys::EFriendRelationship_k_EFriendRelationshipRequestRecipient as i32,
+    Friend = sys::EFriendRelationship_k_EFriendRelationshipFriend as i32,
+    RequestInitiator = sys::EFriendRelationship_k_EFriendRelationshipRequestInitiator as i32,
+    Ignored = sys::EFriendRelationship_k_EFriendRelationshipIgnored as i32,
+    IgnoredFriend = sys::EFriendRelationship_k_EFriendRelationshipIgnoredFriend as i32,
+    SuggestedDeprecated = sys::EFriendRelationship_k_EFriendRelationshipSuggested_DEPRECATED as i32,
+}
+
+impl FriendRelationship {
+    pub(crate) fn from_inner(inner: sys::EFriendRelationship) -> Self {
+        FriendRelationship::from_i32(inner as i32)
+            .unwrap_or_else(|| panic!("Unknown EFriendRelationship discriminant: {}", inner))
+    }
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#EPersonaState>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Primitive)]
+#[repr(i32)]
+pub enum PersonaState {
+    Offline = sys::EPersonaState_k_EPersonaStateOffline as i32,
+    Online = sys::EPersonaState_k_EPersonaStateOnline as i32,
+    Busy = sys::EPersonaState_k_EPersonaStateBusy as i32,
+    Away = sys::EPersonaState_k_EPersonaStateAway as i32,
+    Snooze = sys::EPersonaState_k_EPersonaStateSnooze as i32,
+    LookingToTrade = sys::EPersonaState_k_EPersonaStateLookingToTrade as i32,
+    LookingToPlay = sys::EPersonaState_k_EPersonaStateLookingToPlay as i32,
+    Invisible = sys::EPersonaState_k_EPersonaStateInvisible as i32,
+}
+
+impl PersonaState {
+    pub(crate) fn from_inner(inner: sys::EPersonaState) -> Self {
+        PersonaState::from_i32(inner as i32)
+            .unwrap_or_else(|| panic!("Unknown EPersonaState discriminant: {}", inner))
+    }
+}
+
+/// Identifies a game, which is usually a Steam app, but can also be a mod or a shortcut to a
+/// non-Steam game.
+///
+/// <https://partner.steamgames.com/doc/api/SteamTypes#CGameID>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct GameId(pub u64);
+
+impl GameId {
+    /// Returns the app ID, which is stored in the lower 24 bits of the game ID.
+    pub fn app_id(self) -> AppId {
+        AppId((self.0 & 0xFF_FFFF) as u32)
+    }
+}
+
+impl From<u64> for GameId {
+    fn from(x: u64) -> GameId {
+        GameId(x)
+    }
+}
+
+impl From<GameId> for u64 {
+    fn from(x: GameId) -> u64 {
+        x.0
+    }
+}
+
+/// The game a friend is playing.
+///
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#FriendGameInfo_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct FriendGameInfo {
+    pub game_id: GameId,
+    /// The address of the game server the friend is on, if any.
+    pub game_server: Option<SocketAddrV4>,
+    /// The query port of the game server the friend is on, if any.
+    pub query_port: Option<u16>,
+    /// The lobby the friend is in, if any.
+    pub lobby: Option<SteamId>,
+}
+
+/// The size of a user's avatar.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum AvatarSize {
+    /// 32x32 pixels
+    Small,
+    /// 64x64 pixels
+    Medium,
+    /// 184x184 pixels
+    Large,
+}
+
+/// An avatar image, stored as tightly packed 8-bit RGBA pixels in row-major order.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct AvatarImage {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// A user the current user has recently played with.
+///
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#GetCoplayFriend>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CoplayEntry {
+    pub steam_id: SteamId,
+    /// When the users last played together.
+    pub time: DateTime<Utc>,
+    /// The app the users last played together.
+    pub app_id: AppId,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, snafu::Snafu)]
+#[snafu(module)]
+pub enum SetRichPresenceError {
+    /// The key or value contains nul byte(s)
+    #[snafu(display("The rich presence key or value contains nul byte(s): {}", source))]
+    Nul { source: NulError },
+
+    /// The key is too long
+    #[snafu(display(
+        "The rich presence key has a length of {} bytes, which is over the {} byte limit",
+        length,
+        sys::k_cchMaxRichPresenceKeyLength - 1
+    ))]
+    KeyTooLong { length: usize },
+
+    /// The value is too long
+    #[snafu(display(
+        "The rich presence value has a length of {} bytes, which is over the {} byte limit",
+        length,
+        sys::k_cchMaxRichPresenceValueLength - 1
+    ))]
+    ValueTooLong { length: usize },
+
+    /// `SetRichPresence()` failed, for example because the maximum number of keys is already set
+    #[snafu(display("SetRichPresence() failed"))]
+    SetRichPresence,
+}
+
+pub(crate) fn friends(client: &Client, flags: FriendFlags) -> Vec<SteamId> {
+    unsafe {
+        let count =
+            sys::SteamAPI_ISteamFriends_GetFriendCount(*client.0.friends, flags.bits() as i32);
+
+        (0..count)
+            .map(|i| {
+                sys::SteamAPI_ISteamFriends_GetFriendByIndex(
+                    *client.0.friends,
+                    i,
+                    flags.bits() as i32,
+                )
+                .into()
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn friend_relationship(client: &Client, steam_id: SteamId) -> FriendRelationship {
+    let relationship = unsafe {
+        sys::SteamAPI_ISteamFriends_GetFriendRelationship(*client.0.friends, steam_id.as_u64())
+    };
+
+    FriendRelationship::from_inner(relationship)
+}
+
+pub(crate) fn persona_state(client: &Client, steam_id: SteamId) -> PersonaState {
+    let state = unsafe {
+        sys::SteamAPI_ISteamFriends_GetFriendPersonaState(*client.0.friends, steam_id.as_u64())
+    };
+
+    PersonaState::from_inner(state)
+}
+
+pub(crate) fn friend_game_played(client: &Client, steam_id: SteamId) -> Option<FriendGameInfo> {
+    let mut info: MaybeUninit<sys::FriendGameInfo_t> = MaybeUninit::uninit();
+    let in_game = unsafe {
+        sys::SteamAPI_ISteamFriends_GetFriendGamePlayed(
+            *client.0.friends,
+            steam_id.as_u64(),
+            info.as_mut_ptr(),
+        )
+    };
+    if !in_game {
+        return None;
+    }
+
+    let info = unsafe { info.assume_init() };
+    let game_id = unsafe { info.m_gameID.__bindgen_anon_1.m_ulGameID };
+    let game_server = if info.m_unGameIP == 0 {
+        None
+    } else {
+        Some(SocketAddrV4::new(
+            Ipv4Addr::from(info.m_unGameIP),
+            info.m_usGamePort,
+        ))
+    };
+    let lobby = SteamId::from(info.m_steamIDLobby);
+
+    Some(FriendGameInfo {
+        game_id: GameId(game_id),
+        game_server,
+        query_port: Some(info.m_usQueryPort).filter(|&port| port != 0),
+        lobby: Some(lobby).filter(|lobby| lobby.as_u64() != 0),
+    })
+}
+
+pub(crate) fn steam_level(client: &Client, steam_id: SteamId) -> Option<u32> {
+    let level = unsafe {
+        sys::SteamAPI_ISteamFriends_GetFriendSteamLevel(*client.0.friends, steam_id.as_u64())
+    };
+
+    if level > 0 {
+        Some(level as u32)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn player_nickname(client: &Client, steam_id: SteamId) -> Option<String> {
+    unsafe {
+        let nickname =
+            sys::SteamAPI_ISteamFriends_GetPlayerNickname(*client.0.friends, steam_id.as_u64());
+        if nickname.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(nickname).to_string_lossy().into_owned())
+        }
+    }
+}
+
+/// Returns the Steam image handle of a user's avatar: `0` if no avatar is available, and `-1` if
+/// the avatar is still being loaded.
+pub(crate) fn avatar_handle(client: &Client, steam_id: SteamId, size: AvatarSize) -> i32 {
+    let friends = *client.0.friends;
+    let steam_id = steam_id.as_u64();
+    unsafe {
+        match size {
+            AvatarSize::Small => {
+                sys::SteamAPI_ISteamFriends_GetSmallFriendAvatar(friends, steam_id)
+            }
+            AvatarSize::Medium => {
+                sys::SteamAPI_ISteamFriends_GetMediumFriendAvatar(friends, steam_id)
+            }
+            AvatarSize::Large => {
+                sys::SteamAPI_ISteamFriends_GetLargeFriendAvatar(friends, steam_id)
+            }
+        }
+    }
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamUtils#GetImageRGBA>
+pub(crate) fn avatar_image(client: &Client, handle: i32) -> Option<AvatarImage> {
+    let utils = *client.0.utils;
+    let mut width = 0;
+    let mut height = 0;
+    unsafe {
+        if !sys::SteamAPI_ISteamUtils_GetImageSize(utils, handle, &mut width, &mut height) {
+            return None;
+        }
+
+        let mut rgba = vec![0; width as usize * height as usize * 4];
+        let buffer_size = i32::try_from(rgba.len()).ok()?;
+        if !sys::SteamAPI_ISteamUtils_GetImageRGBA(utils, handle, rgba.as_mut_ptr(), buffer_size) {
+            return None;
+        }
+
+        Some(AvatarImage {
+            width,
+            height,
+            rgba,
+        })
+    }
+}
+
+pub(crate) fn set_rich_presence(
+    client: &Client,
+    key: Vec<u8>,
+    value: Vec<u8>,
+) -> Result<(), SetRichPresenceError> {
+    let key = CString::new(key).context(set_rich_presence_error::NulSnafu)?;
+    let value = CString::new(value).context(set_rich_presence_error::NulSnafu)?;
+    ensure!(
+        key.as_bytes().len() < sys::k_cchMaxRichPresenceKeyLength as usize,
+        set_rich_presence_error::KeyTooLongSnafu {
+            length: key.as_bytes().len()
+        }
+    );
+    ensure!(
+        value.as_bytes().len() < sys::k_cchMaxRichPresenceValueLength as usize,
+        set_rich_presence_error::ValueTooLongSnafu {
+            length: value.as_bytes().len()
+        }
+    );
+
+    let success = unsafe {
+        sys::SteamAPI_ISteamFriends_SetRichPresence(*client.0.friends, key.as_ptr(), value.as_ptr())
+    };
+    ensure!(success, set_rich_presence_error::SetRichPresenceSnafu);
+
+    Ok(())
+}
+
+pub(crate) fn clear_rich_presence(client: &Client) {
+    unsafe { sys::SteamAPI_ISteamFriends_ClearRichPresence(*client.0.friends) }
+}
+
+pub(crate) fn friend_rich_presence(
+    client: &Client,
+    steam_id: SteamId,
+    key: &str,
+) -> Option<String> {
+    // A key containing nul bytes can't have been set
+    let key = CString::new(key).ok()?;
+    let value = unsafe {
+        let value = sys::SteamAPI_ISteamFriends_GetFriendRichPresence(
+            *client.0.friends,
+            steam_id.as_u64(),
+            key.as_ptr(),
+        );
+
+        CStr::from_ptr(value).to_string_lossy().into_owned()
+    };
+
+    Some(value).filter(|value| !value.is_empty())
+}
+
+pub(crate) fn friend_rich_presence_keys(client: &Client, steam_id: SteamId) -> Vec<String> {
+    unsafe {
+        let count = sys::SteamAPI_ISteamFriends_GetFriendRichPresenceKeyCount(
+            *client.0.friends,
+            steam_id.as_u64(),
+        );
+
+        (0..count)
+            .map(|i| {
+                let key = sys::SteamAPI_ISteamFriends_GetFriendRichPresenceKeyByIndex(
+                    *client.0.friends,
+                    steam_id.as_u64(),
+                    i,
+                );
+
+                CStr::from_ptr(key).to_string_lossy().into_owned()
+            })
+            .filter(|key| !key.is_empty())
+            .collect()
+    }
+}
+
+pub(crate) async fn request_friend_rich_presence(
+    client: &Client,
+    steam_id: SteamId,
+    timeout: Duration,
+) -> bool {
+    let updates = client
+        .on_friend_rich_presence_update()
+        .filter(|update| future::ready(update.steam_id == steam_id))
+        .take_until(client.delay(timeout));
+    pin_mut!(updates);
+    unsafe {
+        sys::SteamAPI_ISteamFriends_RequestFriendRichPresence(*client.0.friends, steam_id.as_u64())
+    };
+
+    updates.next().await.is_some()
+}
+
+pub(crate) fn set_played_with(client: &Client, steam_id: SteamId) {
+    unsafe { sys::SteamAPI_ISteamFriends_SetPlayedWith(*client.0.friends, steam_id.as_u64()) }
+}
+
+pub(crate) fn coplay_friends(client: &Client) -> Vec<CoplayEntry> {
+    let count = unsafe { sys::SteamAPI_ISteamFriends_GetCoplayFriendCount(*client.0.friends) };
+
+    (0..count)
+        .filter_map(|i| {
+            let steam_id =
+                unsafe { sys::SteamAPI_ISteamFriends_GetCoplayFriend(*client.0.friends, i) };
+
+            coplay_entry(client, steam_id.into())
+        })
+        .collect()
+}
+
+pub(crate) fn coplay_entry(client: &Client, steam_id: SteamId) -> Option<CoplayEntry> {
+    let (time, app_id) = unsafe {
+        (
+            sys::SteamAPI_ISteamFriends_GetFriendCoplayTime(*client.0.friends, steam_id.as_u64()),
+            sys::SteamAPI_ISteamFriends_GetFriendCoplayGame(*client.0.friends, steam_id.as_u64()),
+        )
+    };
+    if time <= 0 {
+        return None;
+    }
+
+    Some(CoplayEntry {
+        steam_id,
+        time: Utc.timestamp_opt(i64::from(time), 0).single()?,
+        app_id: app_id.into(),
+    })
+}
+
+/// Caches the persona names fetched by [`Client::persona_names`] and [`SteamId::persona_name`].
+///
+/// Cached names are updated by the worker thread when it dispatches a [`PersonaStateChange`] with
+/// a new name. The cache holds at most [`MAX_LEN`](Self::MAX_LEN) names, beyond which an
+/// arbitrary name is evicted for each new one.
+pub(crate) struct PersonaNameCache {
+    names: Mutex<FnvHashMap<SteamId, String>>,
+}
+
+impl PersonaNameCache {
+    const MAX_LEN: usize = 4096;
+
+    pub(crate) fn new() -> Self {
+        PersonaNameCache {
+            names: Mutex::new(FnvHashMap::default()),
+        }
+    }
+
+    /// Updates the cached name of the user whose persona state changed, if it's cached.
+    pub(crate) fn update(&self, client: &Client, change: PersonaStateChange) {
+        if change.change_flags.contains(PersonaStateChangeFlags::NAME) {
+            if let Some(name) = self.names.lock().get_mut(&change.steam_id) {
+                *name = persona_name(client, change.steam_id);
+            }
+        }
+    }
+
+    fn get(&self, steam_id: SteamId) -> Option<String> {
+        self.names.lock().get(&steam_id).cloned()
+    }
+
+    fn insert(&self, steam_id: SteamId, name: String) {
+        let mut names = self.names.lock();
+        if names.len() >= Self::MAX_LEN && !names.contains_key(&steam_id) {
+            let evicted = *names.keys().next().unwrap();
+            names.remove(&evicted);
+        }
+        names.insert(steam_id, name);
+    }
+}
+
+impl Debug for PersonaNameCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("PersonaNameCache")
+            .field("names", &self.names)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Without a `timeout`, waits until the information of every user has arrived or Steam shuts
+/// down.
+pub(crate) async fn persona_names(
+    client: &Client,
+    steam_ids: Vec<SteamId>,
+    timeout: Option<Duration>,
+) -> Vec<Option<String>> {
+    let cache = &client.0.persona_name_cache;
+    let name_changes = client.on_persona_state_changed().filter(|change| {
+        future::ready(change.change_flags.contains(PersonaStateChangeFlags::NAME))
+    });
+
+    // Collect the names locally, so that the result doesn't depend on what happens to the cache
+    // in the meantime
+    let mut names: FnvHashMap<SteamId, String> = FnvHashMap::default();
+    let mut pending: FnvHashSet<SteamId> = FnvHashSet::default();
+    for &steam_id in &steam_ids {
+        if names.contains_key(&steam_id) || pending.contains(&steam_id) {
+            continue;
+        }
+
+        if let Some(name) = cache.get(steam_id) {
+            names.insert(steam_id, name);
+            continue;
+        }
+
+        let request_in_progress = unsafe {
+            sys::SteamAPI_ISteamFriends_RequestUserInformation(
+                *client.0.friends,
+                steam_id.as_u64(),
+                true,
+            )
+        };
+        if request_in_progress {
+            pending.insert(steam_id);
+        } else {
+            let name = persona_name(client, steam_id);
+            cache.insert(steam_id, name.clone());
+            names.insert(steam_id, name);
+        }
+    }
+
+    let timeout = match timeout {
+        Some(timeout) => client.delay(timeout).left_future(),
+        None => future::pending().right_future(),
+    };
+    let name_changes = name_changes.take_until(timeout);
+    pin_mut!(name_changes);
+    while !pending.is_empty() {
+        match name_changes.next().await {
+            Some(change) => {
+                if pending.remove(&change.steam_id) {
+                    let name = persona_name(client, change.steam_id);
+                    cache.insert(change.steam_id, name.clone());
+                    names.insert(change.steam_id, name);
+                }
+            }
+            None => break,
+        }
+    }
+
+    steam_ids
+        .iter()
+        .map(|steam_id| names.get(steam_id).cloned())
+        .collect()
+}
+
+pub(crate) fn persona_name(client: &Client, steam_id: SteamId) -> String {
+    unsafe {
+        let name =
+            sys::SteamAPI_ISteamFriends_GetFriendPersonaName(*client.0.friends, steam_id.as_u64());
+
+        CStr::from_ptr(name).to_string_lossy().into_owned()
+    }
+}
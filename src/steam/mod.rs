@@ -1,5 +1,9 @@
 pub use common::*;
 
+pub mod apps;
+pub mod clans;
+pub mod friends;
+pub mod overlay;
 pub mod remote_storage;
 pub mod ugc;
 pub mod user_stats;
@@ -0,0 +1,180 @@
+use crate::{AppId, Client, SteamId};
+use std::ffi::CString;
+use steamworks_sys as sys;
+
+/// A dialog that can be opened in the Steam overlay with [`Client::activate_game_overlay`].
+///
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#ActivateGameOverlay>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum GameOverlayDialog {
+    Friends,
+    Community,
+    Players,
+    Settings,
+    OfficialGameGroup,
+    Stats,
+    Achievements,
+}
+
+impl GameOverlayDialog {
+    fn as_str(self) -> &'static str {
+        match self {
+            GameOverlayDialog::Friends => "friends",
+            GameOverlayDialog::Community => "community",
+            GameOverlayDialog::Players => "players",
+            GameOverlayDialog::Settings => "settings",
+            GameOverlayDialog::OfficialGameGroup => "officialgamegroup",
+            GameOverlayDialog::Stats => "stats",
+            GameOverlayDialog::Achievements => "achievements",
+        }
+    }
+}
+
+/// A dialog about a user that can be opened in the Steam overlay with
+/// [`Client::activate_overlay_to_user`].
+///
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#ActivateGameOverlayToUser>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum UserOverlayDialog {
+    /// The user's Steam community profile
+    SteamId,
+    Chat,
+    JoinTrade,
+    Stats,
+    Achievements,
+    FriendAdd,
+    FriendRemove,
+    FriendRequestAccept,
+    FriendRequestIgnore,
+}
+
+impl UserOverlayDialog {
+    fn as_str(self) -> &'static str {
+        match self {
+            UserOverlayDialog::SteamId => "steamid",
+            UserOverlayDialog::Chat => "chat",
+            UserOverlayDialog::JoinTrade => "jointrade",
+            UserOverlayDialog::Stats => "stats",
+            UserOverlayDialog::Achievements => "achievements",
+            UserOverlayDialog::FriendAdd => "friendadd",
+            UserOverlayDialog::FriendRemove => "friendremove",
+            UserOverlayDialog::FriendRequestAccept => "friendrequestaccept",
+            UserOverlayDialog::FriendRequestIgnore => "friendrequestignore",
+        }
+    }
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#EActivateGameOverlayToWebPageMode>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[repr(i32)]
+pub enum WebPageMode {
+    /// The browser is opened next to all other overlay windows, and stays open when the overlay
+    /// is closed.
+    Default =
+        sys::EActivateGameOverlayToWebPageMode_k_EActivateGameOverlayToWebPageMode_Default as i32,
+    /// The browser is opened on its own, and the overlay closes when the browser is closed.
+    Modal = sys::EActivateGameOverlayToWebPageMode_k_EActivateGameOverlayToWebPageMode_Modal as i32,
+}
+
+impl From<WebPageMode> for sys::EActivateGameOverlayToWebPageMode {
+    fn from(x: WebPageMode) -> Self {
+        x as sys::EActivateGameOverlayToWebPageMode
+    }
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#EOverlayToStoreFlag>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[repr(i32)]
+pub enum OverlayToStoreFlag {
+    None = sys::EOverlayToStoreFlag_k_EOverlayToStoreFlag_None as i32,
+    AddToCart = sys::EOverlayToStoreFlag_k_EOverlayToStoreFlag_AddToCart as i32,
+    AddToCartAndShow = sys::EOverlayToStoreFlag_k_EOverlayToStoreFlag_AddToCartAndShow as i32,
+}
+
+impl From<OverlayToStoreFlag> for sys::EOverlayToStoreFlag {
+    fn from(x: OverlayToStoreFlag) -> Self {
+        x as sys::EOverlayToStoreFlag
+    }
+}
+
+/// <https://partner.steamgames.com/doc/api/steam_api#ENotificationPosition>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[repr(i32)]
+pub enum NotificationPosition {
+    TopLeft = sys::ENotificationPosition_k_EPositionTopLeft as i32,
+    TopRight = sys::ENotificationPosition_k_EPositionTopRight as i32,
+    BottomLeft = sys::ENotificationPosition_k_EPositionBottomLeft as i32,
+    BottomRight = sys::ENotificationPosition_k_EPositionBottomRight as i32,
+}
+
+impl From<NotificationPosition> for sys::ENotificationPosition {
+    fn from(x: NotificationPosition) -> Self {
+        x as sys::ENotificationPosition
+    }
+}
+
+pub(crate) fn activate_game_overlay(client: &Client, dialog: GameOverlayDialog) {
+    let dialog = CString::new(dialog.as_str()).unwrap();
+    unsafe { sys::SteamAPI_ISteamFriends_ActivateGameOverlay(*client.0.friends, dialog.as_ptr()) }
+}
+
+pub(crate) fn activate_overlay_to_user(
+    client: &Client,
+    dialog: UserOverlayDialog,
+    steam_id: SteamId,
+) {
+    let dialog = CString::new(dialog.as_str()).unwrap();
+    unsafe {
+        sys::SteamAPI_ISteamFriends_ActivateGameOverlayToUser(
+            *client.0.friends,
+            dialog.as_ptr(),
+            steam_id.as_u64(),
+        )
+    }
+}
+
+pub(crate) fn activate_overlay_to_web_page(client: &Client, url: &str, mode: WebPageMode) {
+    let url = CString::new(url).expect("URL contains nul byte(s)");
+    unsafe {
+        sys::SteamAPI_ISteamFriends_ActivateGameOverlayToWebPage(
+            *client.0.friends,
+            url.as_ptr(),
+            mode.into(),
+        )
+    }
+}
+
+pub(crate) fn activate_overlay_to_store(client: &Client, app_id: AppId, flag: OverlayToStoreFlag) {
+    unsafe {
+        sys::SteamAPI_ISteamFriends_ActivateGameOverlayToStore(
+            *client.0.friends,
+            app_id.into(),
+            flag.into(),
+        )
+    }
+}
+
+pub(crate) fn activate_invite_dialog(client: &Client, lobby: SteamId) {
+    unsafe {
+        sys::SteamAPI_ISteamFriends_ActivateGameOverlayInviteDialog(
+            *client.0.friends,
+            lobby.as_u64(),
+        )
+    }
+}
+
+pub(crate) fn is_overlay_enabled(client: &Client) -> bool {
+    unsafe { sys::SteamAPI_ISteamUtils_IsOverlayEnabled(*client.0.utils) }
+}
+
+pub(crate) fn set_overlay_notification_position(client: &Client, position: NotificationPosition) {
+    unsafe {
+        sys::SteamAPI_ISteamUtils_SetOverlayNotificationPosition(*client.0.utils, position.into())
+    }
+}
+
+pub(crate) fn set_overlay_notification_inset(client: &Client, horizontal: i32, vertical: i32) {
+    unsafe {
+        sys::SteamAPI_ISteamUtils_SetOverlayNotificationInset(*client.0.utils, horizontal, vertical)
+    }
+}
@@ -1,9 +1,14 @@
 use crate::steam::SteamResult;
 use crate::string_ext::FromUtf8NulTruncating;
 use crate::{AppId, Client, SteamId};
-use futures::Future;
+use futures::{Future, Stream};
+use genawaiter::sync::Gen;
 use snafu::{ensure, ResultExt};
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
+use std::mem::MaybeUninit;
+use std::os::raw::c_char;
+use std::ptr;
+use std::time::Duration;
 use steamworks_sys as sys;
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -45,14 +50,99 @@ impl UgcHandle {
             Ok(DownloadUGCResult {
                 app_id: response.m_nAppID.into(),
                 size_in_bytes: response.m_nSizeInBytes,
-                filename: String::from_utf8_nul_truncating(&response.m_pchFileName[..]).expect(
-                    "Filename returned in RemoteStorageDownloadUGCResult_t was not valid UTF-8",
-                ),
+                filename: String::from_utf8_nul_truncating(&response.m_pchFileName[..])
+                    .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()),
                 steam_id_owner: SteamId::new(response.m_ulSteamIDOwner),
             })
         }
     }
 
+    /// <https://partner.steamgames.com/doc/api/ISteamRemoteStorage#GetUGCDownloadProgress>
+    ///
+    /// Returns `None` if the content is not being downloaded.
+    pub fn download_progress(self, client: &Client) -> Option<UgcDownloadProgress> {
+        let mut bytes_downloaded = 0;
+        let mut bytes_expected = 0;
+        let success = unsafe {
+            sys::SteamAPI_ISteamRemoteStorage_GetUGCDownloadProgress(
+                *client.0.remote_storage,
+                self.0,
+                &mut bytes_downloaded,
+                &mut bytes_expected,
+            )
+        };
+
+        if success {
+            Some(UgcDownloadProgress {
+                bytes_downloaded,
+                bytes_expected,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Polls [`download_progress`](Self::download_progress) every `interval`, yielding the
+    /// progress until the download is complete.
+    ///
+    /// The stream waits for Steam to start reporting progress, so it can be polled before the
+    /// download is started. If the download never starts, the stream doesn't end either.
+    pub fn download_progress_updates(
+        self,
+        client: Client,
+        interval: Duration,
+    ) -> impl Stream<Item = UgcDownloadProgress> + Send {
+        Gen::new(|co| async move {
+            let mut started = false;
+            loop {
+                match self.download_progress(&client) {
+                    Some(progress) => {
+                        started = true;
+                        co.yield_(progress).await;
+                        if progress.is_complete() {
+                            break;
+                        }
+                    }
+                    None if started => break,
+                    None => {}
+                }
+
+                client.delay(interval).await;
+            }
+        })
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamRemoteStorage#GetUGCDetails>
+    ///
+    /// Returns `None` if the details are not available, which is the case until the content has
+    /// been downloaded.
+    pub fn details(self, client: &Client) -> Option<DownloadUGCResult> {
+        let mut app_id = 0;
+        let mut name: *mut c_char = ptr::null_mut();
+        let mut size_in_bytes = 0;
+        let mut steam_id_owner: MaybeUninit<sys::CSteamID> = MaybeUninit::uninit();
+        unsafe {
+            let success = sys::SteamAPI_ISteamRemoteStorage_GetUGCDetails(
+                *client.0.remote_storage,
+                self.0,
+                &mut app_id,
+                &mut name,
+                &mut size_in_bytes,
+                steam_id_owner.as_mut_ptr(),
+            );
+            if !success || name.is_null() {
+                return None;
+            }
+
+            Some(DownloadUGCResult {
+                app_id: app_id.into(),
+                size_in_bytes,
+                filename: CStr::from_ptr(name).to_string_lossy().into_owned(),
+                steam_id_owner: steam_id_owner.assume_init().into(),
+            })
+        }
+    }
+
     pub(crate) fn from_inner(handle: sys::UGCHandle_t) -> Option<Self> {
         if handle == sys::k_UGCHandleInvalid {
             None
@@ -64,10 +154,25 @@ impl UgcHandle {
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct DownloadUGCResult {
-    app_id: AppId,
-    size_in_bytes: i32,
-    filename: String,
-    steam_id_owner: SteamId,
+    pub app_id: AppId,
+    pub size_in_bytes: i32,
+    pub filename: String,
+    pub steam_id_owner: SteamId,
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamRemoteStorage#GetUGCDownloadProgress>
+#[derive(Debug, Copy, Clone, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct UgcDownloadProgress {
+    pub bytes_downloaded: i32,
+
+    /// This can be `0` if the transfer hasn't started yet.
+    pub bytes_expected: i32,
+}
+
+impl UgcDownloadProgress {
+    pub fn is_complete(&self) -> bool {
+        self.bytes_expected > 0 && self.bytes_downloaded >= self.bytes_expected
+    }
 }
 
 #[derive(Debug, snafu::Snafu)]
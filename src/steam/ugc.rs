@@ -1,22 +1,38 @@
+#![allow(clippy::unnecessary_cast)]
+
 use crate::steam::remote_storage::UgcHandle;
-use crate::steam::{AppId, SteamId, SteamResult};
+use crate::steam::{AccountId, AppId, SteamId, SteamResult};
 use crate::string_ext::FromUtf8NulTruncating;
 use crate::Client;
+use bitflags::bitflags;
 use chrono::offset::TimeZone;
 use chrono::{DateTime, Utc};
 use derive_more::{From, Into};
 use enum_primitive_derive::Primitive;
-use futures::Stream;
-use genawaiter::sync::Gen;
+use futures::future::{self, Either};
+use futures::{pin_mut, Stream, StreamExt};
+use genawaiter::sync::{Co, Gen};
 use num_traits::FromPrimitive;
-use std::collections::BTreeMap;
+use snafu::ensure;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
 use std::ffi::CString;
 use std::mem::MaybeUninit;
 use std::os::raw::c_char;
+use std::path::PathBuf;
+use std::string::FromUtf8Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use std::{cmp, ptr, str};
 use steamworks_sys as sys;
 
+pub use mod_set::*;
+pub use tags::*;
+
+mod mod_set;
+mod tags;
+
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub enum QueryType {
     RankedByVote,
@@ -114,6 +130,7 @@ pub struct UgcDetails {
     pub visibility: PublishedFileVisibility,
     pub banned: bool,
     pub accepted_for_use: bool,
+    #[deprecated(note = "use `tags.is_truncated()` instead")]
     pub tags_truncated: bool,
     pub tags: Tags,
     pub file: Option<UgcHandle>,
@@ -127,9 +144,77 @@ pub struct UgcDetails {
     pub votes_down: u32,
     pub score: f32,
     pub num_children: u32,
+    /// Only populated if the query was configured with `return_key_value_tags()`.
+    pub key_value_tags: Vec<(String, String)>,
+    /// Only populated if the query was configured with `return_metadata()`.
+    pub metadata: String,
+    /// Only populated if the query was configured with `return_children()`.
+    pub children: Vec<PublishedFileId>,
+    /// Only populated if the query was configured with `return_additional_previews()`. Previews
+    /// of types unknown to this crate are left out.
+    pub additional_previews: Vec<AdditionalPreview>,
+    pub statistics: ItemStatistics,
+}
+
+/// An additional preview of a workshop item, besides its main preview image.
+///
+/// See <https://partner.steamgames.com/doc/api/ISteamUGC#GetQueryUGCAdditionalPreview>.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct AdditionalPreview {
+    /// The URL of the preview, or the video ID for YouTube previews.
+    pub url_or_video_id: String,
+    pub original_file_name: String,
+    pub preview_type: ItemPreviewType,
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Primitive)]
+#[repr(i32)]
+pub enum ItemPreviewType {
+    Image = sys::EItemPreviewType_k_EItemPreviewType_Image as i32,
+    YouTubeVideo = sys::EItemPreviewType_k_EItemPreviewType_YouTubeVideo as i32,
+    Sketchfab = sys::EItemPreviewType_k_EItemPreviewType_Sketchfab as i32,
+    EnvironmentMapHorizontalCross =
+        sys::EItemPreviewType_k_EItemPreviewType_EnvironmentMap_HorizontalCross as i32,
+    EnvironmentMapLatLong = sys::EItemPreviewType_k_EItemPreviewType_EnvironmentMap_LatLong as i32,
+}
+
+impl From<ItemPreviewType> for sys::EItemPreviewType {
+    fn from(x: ItemPreviewType) -> Self {
+        x as sys::EItemPreviewType
+    }
+}
+
+impl ItemPreviewType {
+    /// Returns `None` for preview types that were added to Steam after this crate was written.
+    pub(crate) fn from_inner(inner: sys::EItemPreviewType) -> Option<Self> {
+        ItemPreviewType::from_i32(inner as i32)
+    }
+}
+
+/// The statistics of a workshop item.
+///
+/// The playtime statistics are only populated if the query was configured with
+/// `return_playtime_stats()`. See
+/// <https://partner.steamgames.com/doc/api/ISteamUGC#EItemStatistic>.
+#[derive(Debug, Copy, Clone, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ItemStatistics {
+    pub num_subscriptions: u64,
+    pub num_favorites: u64,
+    pub num_followers: u64,
+    pub num_unique_subscriptions: u64,
+    pub num_unique_favorites: u64,
+    pub num_unique_followers: u64,
+    pub num_unique_website_views: u64,
+    pub report_score: u64,
+    pub num_seconds_played: u64,
+    pub num_playtime_sessions: u64,
+    pub num_comments: u64,
+    pub num_seconds_played_during_time_period: u64,
+    pub num_playtime_sessions_during_time_period: u64,
 }
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, From, Into)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PublishedFileId(pub u64);
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Primitive)]
@@ -154,6 +239,12 @@ pub enum WorkshopFileType {
     GameManagedItem = sys::EWorkshopFileType_k_EWorkshopFileTypeGameManagedItem as i32,
 }
 
+impl From<WorkshopFileType> for sys::EWorkshopFileType {
+    fn from(x: WorkshopFileType) -> Self {
+        x as sys::EWorkshopFileType
+    }
+}
+
 impl WorkshopFileType {
     pub(crate) fn from_inner(inner: sys::EWorkshopFileType) -> Self {
         WorkshopFileType::from_i32(inner as i32)
@@ -172,6 +263,12 @@ pub enum PublishedFileVisibility {
     sys::ERemoteStoragePublishedFileVisibility_k_ERemoteStoragePublishedFileVisibilityPrivate as i32,
 }
 
+impl From<PublishedFileVisibility> for sys::ERemoteStoragePublishedFileVisibility {
+    fn from(x: PublishedFileVisibility) -> Self {
+        x as sys::ERemoteStoragePublishedFileVisibility
+    }
+}
+
 impl PublishedFileVisibility {
     pub(crate) fn from_inner(inner: sys::ERemoteStoragePublishedFileVisibility) -> Self {
         PublishedFileVisibility::from_i32(inner as i32).unwrap_or_else(|| {
@@ -183,33 +280,47 @@ impl PublishedFileVisibility {
     }
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct Tags(String);
-
-impl Tags {
-    pub fn into_inner(self) -> String {
-        self.0
-    }
-
-    pub fn as_str(&self) -> &str {
-        &self.0
-    }
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[repr(i32)]
+pub enum UserUgcList {
+    Published = sys::EUserUGCList_k_EUserUGCList_Published as i32,
+    VotedOn = sys::EUserUGCList_k_EUserUGCList_VotedOn as i32,
+    VotedUp = sys::EUserUGCList_k_EUserUGCList_VotedUp as i32,
+    VotedDown = sys::EUserUGCList_k_EUserUGCList_VotedDown as i32,
+    WillVoteLater = sys::EUserUGCList_k_EUserUGCList_WillVoteLater as i32,
+    Favorited = sys::EUserUGCList_k_EUserUGCList_Favorited as i32,
+    Subscribed = sys::EUserUGCList_k_EUserUGCList_Subscribed as i32,
+    UsedOrPlayed = sys::EUserUGCList_k_EUserUGCList_UsedOrPlayed as i32,
+    Followed = sys::EUserUGCList_k_EUserUGCList_Followed as i32,
+}
 
-    pub fn iter(&self) -> impl Iterator<Item = &str> {
-        self.into_iter()
+impl From<UserUgcList> for sys::EUserUGCList {
+    fn from(x: UserUgcList) -> Self {
+        x as sys::EUserUGCList
     }
 }
 
-impl<'a> IntoIterator for &'a Tags {
-    type Item = &'a str;
-    type IntoIter = str::Split<'a, char>;
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[repr(i32)]
+pub enum UserUgcListSortOrder {
+    CreationOrderDesc = sys::EUserUGCListSortOrder_k_EUserUGCListSortOrder_CreationOrderDesc as i32,
+    CreationOrderAsc = sys::EUserUGCListSortOrder_k_EUserUGCListSortOrder_CreationOrderAsc as i32,
+    TitleAsc = sys::EUserUGCListSortOrder_k_EUserUGCListSortOrder_TitleAsc as i32,
+    LastUpdatedDesc = sys::EUserUGCListSortOrder_k_EUserUGCListSortOrder_LastUpdatedDesc as i32,
+    SubscriptionDateDesc =
+        sys::EUserUGCListSortOrder_k_EUserUGCListSortOrder_SubscriptionDateDesc as i32,
+    VoteScoreDesc = sys::EUserUGCListSortOrder_k_EUserUGCListSortOrder_VoteScoreDesc as i32,
+    ForModeration = sys::EUserUGCListSortOrder_k_EUserUGCListSortOrder_ForModeration as i32,
+}
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.0.split(',')
+impl From<UserUgcListSortOrder> for sys::EUserUGCListSortOrder {
+    fn from(x: UserUgcListSortOrder) -> Self {
+        x as sys::EUserUGCListSortOrder
     }
 }
 
 #[derive(Debug, snafu::Snafu)]
+#[snafu(module)]
 pub enum QueryAllUgcError {
     /// Neither the creator App ID nor the consumer App ID was set to the App ID of the currently running application
     #[snafu(display("Neither the creator App ID nor the consumer App ID was set to the App ID of the currently running application"))]
@@ -219,9 +330,589 @@ pub enum QueryAllUgcError {
     #[snafu(display("CreateQueryAllUGCRequest() failed"))]
     CreateQueryAllUGCRequest,
 
+    /// One of the functions configuring the query failed
+    #[snafu(display("{}() failed", function))]
+    ConfigureQuery { function: &'static str },
+
+    /// `SendQueryUGCRequest()` failed
+    #[snafu(display("SendQueryUGCRequest() failed: {}", steam_result))]
+    SendQueryUGCRequest { steam_result: SteamResult },
+
+    /// One of the functions reading the query's results failed
+    #[snafu(display("{}() failed", function))]
+    ReadQueryResult { function: &'static str },
+
+    /// One of the returned item's text fields isn't valid UTF-8
+    #[snafu(display(
+        "The {} of workshop item {} is not valid UTF-8",
+        field,
+        published_file_id.0
+    ))]
+    InvalidUtf8 {
+        published_file_id: PublishedFileId,
+        field: &'static str,
+    },
+}
+
+#[derive(Debug, snafu::Snafu)]
+#[snafu(module)]
+pub enum QueryUserUgcError {
+    /// Neither the creator App ID nor the consumer App ID was set to the App ID of the currently running application
+    #[snafu(display("Neither the creator App ID nor the consumer App ID was set to the App ID of the currently running application"))]
+    AppId,
+
+    /// `CreateQueryUserUGCRequest()` failed
+    #[snafu(display("CreateQueryUserUGCRequest() failed"))]
+    CreateQueryUserUGCRequest,
+
+    /// One of the functions configuring the query failed
+    #[snafu(display("{}() failed", function))]
+    ConfigureQuery { function: &'static str },
+
+    /// `SendQueryUGCRequest()` failed
+    #[snafu(display("SendQueryUGCRequest() failed: {}", steam_result))]
+    SendQueryUGCRequest { steam_result: SteamResult },
+
+    /// One of the functions reading the query's results failed
+    #[snafu(display("{}() failed", function))]
+    ReadQueryResult { function: &'static str },
+
+    /// One of the returned item's text fields isn't valid UTF-8
+    #[snafu(display(
+        "The {} of workshop item {} is not valid UTF-8",
+        field,
+        published_file_id.0
+    ))]
+    InvalidUtf8 {
+        published_file_id: PublishedFileId,
+        field: &'static str,
+    },
+}
+
+#[derive(Debug, snafu::Snafu)]
+#[snafu(module)]
+pub enum QueryUgcDetailsError {
+    /// `CreateQueryUGCDetailsRequest()` failed
+    #[snafu(display("CreateQueryUGCDetailsRequest() failed"))]
+    CreateQueryUGCDetailsRequest,
+
+    /// One of the functions configuring the query failed
+    #[snafu(display("{}() failed", function))]
+    ConfigureQuery { function: &'static str },
+
     /// `SendQueryUGCRequest()` failed
     #[snafu(display("SendQueryUGCRequest() failed: {}", steam_result))]
     SendQueryUGCRequest { steam_result: SteamResult },
+
+    /// One of the functions reading the query's results failed
+    #[snafu(display("{}() failed", function))]
+    ReadQueryResult { function: &'static str },
+}
+
+/// The details of a requested workshop item could not be retrieved.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, snafu::Snafu)]
+#[snafu(module)]
+pub enum UgcItemError {
+    /// The item is unavailable, for example because it was deleted or made private
+    #[snafu(display(
+        "The details of workshop item {} are unavailable: {}",
+        published_file_id.0,
+        steam_result
+    ))]
+    Unavailable {
+        published_file_id: PublishedFileId,
+        steam_result: SteamResult,
+    },
+
+    /// One of the item's text fields isn't valid UTF-8
+    #[snafu(display(
+        "The {} of workshop item {} is not valid UTF-8",
+        field,
+        published_file_id.0
+    ))]
+    InvalidUtf8 {
+        published_file_id: PublishedFileId,
+        field: &'static str,
+    },
+}
+
+/// A text field of a workshop item's details that isn't valid UTF-8.
+#[derive(Debug, Copy, Clone)]
+struct InvalidUtf8Field {
+    published_file_id: PublishedFileId,
+    field: &'static str,
+}
+
+/// A function configuring a query or reading its results returned `false`.
+#[derive(Debug, Copy, Clone)]
+struct QueryFunctionFailed {
+    function: &'static str,
+}
+
+/// Why the details of a query result couldn't be read.
+#[derive(Debug, Copy, Clone)]
+enum QueryResultError {
+    InvalidUtf8(InvalidUtf8Field),
+    Function(QueryFunctionFailed),
+}
+
+impl From<InvalidUtf8Field> for QueryResultError {
+    fn from(e: InvalidUtf8Field) -> Self {
+        QueryResultError::InvalidUtf8(e)
+    }
+}
+
+impl From<QueryFunctionFailed> for QueryResultError {
+    fn from(e: QueryFunctionFailed) -> Self {
+        QueryResultError::Function(e)
+    }
+}
+
+fn check_query_function(success: bool, function: &'static str) -> Result<(), QueryFunctionFailed> {
+    if success {
+        Ok(())
+    } else {
+        Err(QueryFunctionFailed { function })
+    }
+}
+
+/// Decodes the text fields of a workshop item's details, either strictly or lossily.
+#[derive(Debug, Copy, Clone)]
+struct FieldDecoder {
+    published_file_id: PublishedFileId,
+    lossy: bool,
+}
+
+impl FieldDecoder {
+    fn decode(
+        &self,
+        result: Result<String, FromUtf8Error>,
+        field: &'static str,
+    ) -> Result<String, InvalidUtf8Field> {
+        match result {
+            Ok(x) => Ok(x),
+            Err(e) if self.lossy => Ok(String::from_utf8_lossy(e.as_bytes()).into_owned()),
+            Err(_) => Err(InvalidUtf8Field {
+                published_file_id: self.published_file_id,
+                field,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, snafu::Snafu)]
+#[snafu(module)]
+pub enum CreateItemError {
+    /// `CreateItem()` failed
+    #[snafu(display("CreateItem() failed: {}", steam_result))]
+    CreateItem { steam_result: SteamResult },
+}
+
+#[derive(Debug, snafu::Snafu)]
+#[snafu(module)]
+pub enum SubmitItemUpdateError {
+    /// `StartItemUpdate()` failed
+    #[snafu(display("StartItemUpdate() failed"))]
+    StartItemUpdate,
+
+    /// One of the functions setting a property of the item update failed
+    #[snafu(display("{}() failed", function))]
+    SetItemProperty { function: &'static str },
+
+    /// `SubmitItemUpdate()` failed
+    #[snafu(display("SubmitItemUpdate() failed: {}", steam_result))]
+    SubmitItemUpdate { steam_result: SteamResult },
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamUGC#CreateItemResult_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CreatedItem {
+    pub published_file_id: PublishedFileId,
+
+    /// If `true`, the item will stay hidden until the user accepts the Steam Workshop legal
+    /// agreement.
+    pub user_needs_to_accept_workshop_legal_agreement: bool,
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamUGC#SubmitItemUpdateResult_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SubmittedItemUpdate {
+    pub published_file_id: PublishedFileId,
+
+    /// If `true`, the item will stay hidden until the user accepts the Steam Workshop legal
+    /// agreement.
+    pub user_needs_to_accept_workshop_legal_agreement: bool,
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Primitive)]
+#[repr(i32)]
+pub enum ItemUpdateStatus {
+    PreparingConfig = sys::EItemUpdateStatus_k_EItemUpdateStatusPreparingConfig as i32,
+    PreparingContent = sys::EItemUpdateStatus_k_EItemUpdateStatusPreparingContent as i32,
+    UploadingContent = sys::EItemUpdateStatus_k_EItemUpdateStatusUploadingContent as i32,
+    UploadingPreviewFile = sys::EItemUpdateStatus_k_EItemUpdateStatusUploadingPreviewFile as i32,
+    CommittingChanges = sys::EItemUpdateStatus_k_EItemUpdateStatusCommittingChanges as i32,
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamUGC#GetItemUpdateProgress>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ItemUpdateProgress {
+    pub status: ItemUpdateStatus,
+    pub bytes_processed: u64,
+    pub bytes_total: u64,
+}
+
+/// A workshop call, such as `SubscribeItem()` or `DeleteItem()`, completed with an error.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(display("{}() failed: {}", function, steam_result))]
+pub struct UgcCallError {
+    pub function: &'static str,
+    pub steam_result: SteamResult,
+}
+
+#[derive(Debug, snafu::Snafu)]
+#[snafu(module)]
+pub enum DownloadItemError {
+    /// `DownloadItem()` failed to start the download
+    #[snafu(display("DownloadItem() failed to start the download"))]
+    DownloadItem,
+
+    /// The download failed
+    #[snafu(display("The download failed: {}", steam_result))]
+    Download { steam_result: SteamResult },
+}
+
+/// The current user's vote on a workshop item.
+///
+/// <https://partner.steamgames.com/doc/api/ISteamUGC#GetUserItemVoteResult_t>
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum UserItemVote {
+    NotVoted,
+    VotedUp,
+    VotedDown,
+    Skipped,
+}
+
+bitflags! {
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#EItemState>
+    #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct ItemState: u32 {
+        const SUBSCRIBED = sys::EItemState_k_EItemStateSubscribed as u32;
+        const LEGACY_ITEM = sys::EItemState_k_EItemStateLegacyItem as u32;
+        const INSTALLED = sys::EItemState_k_EItemStateInstalled as u32;
+        const NEEDS_UPDATE = sys::EItemState_k_EItemStateNeedsUpdate as u32;
+        const DOWNLOADING = sys::EItemState_k_EItemStateDownloading as u32;
+        const DOWNLOAD_PENDING = sys::EItemState_k_EItemStateDownloadPending as u32;
+    }
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamUGC#GetItemDownloadInfo>
+#[derive(Debug, Copy, Clone, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ItemDownloadProgress {
+    pub bytes_downloaded: u64,
+
+    /// This can be `0` if the download hasn't started yet.
+    pub bytes_total: u64,
+}
+
+impl ItemDownloadProgress {
+    pub fn is_complete(&self) -> bool {
+        self.bytes_total > 0 && self.bytes_downloaded >= self.bytes_total
+    }
+}
+
+/// <https://partner.steamgames.com/doc/api/ISteamUGC#GetItemInstallInfo>
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ItemInstallInfo {
+    /// The folder the item's content is installed to.
+    pub folder: PathBuf,
+    pub size_on_disk: u64,
+    /// When the installed version of the item was last updated.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Query settings shared by all kinds of UGC queries.
+#[derive(Debug, Clone, Default)]
+struct QueryConfig {
+    max_results: Option<u32>,
+    match_any_tag: bool,
+    tags: BTreeMap<CString, bool>,
+    tag_groups: Vec<Tags>,
+    key_value_tags: Vec<(CString, CString)>,
+    return_long_description: bool,
+    return_key_value_tags: bool,
+    return_metadata: bool,
+    return_children: bool,
+    return_additional_previews: bool,
+    return_playtime_stats: Option<u32>,
+    return_only_ids: bool,
+    return_total_only: bool,
+    lossy_utf8: bool,
+    language: Option<CString>,
+    allow_cached_response: Option<Duration>,
+}
+
+impl QueryConfig {
+    unsafe fn apply(
+        &self,
+        ugc: *mut sys::ISteamUGC,
+        handle: sys::UGCQueryHandle_t,
+    ) -> Result<(), QueryFunctionFailed> {
+        let success = sys::SteamAPI_ISteamUGC_SetReturnLongDescription(
+            ugc,
+            handle,
+            self.return_long_description,
+        );
+        check_query_function(success, "SetReturnLongDescription")?;
+
+        let success =
+            sys::SteamAPI_ISteamUGC_SetReturnKeyValueTags(ugc, handle, self.return_key_value_tags);
+        check_query_function(success, "SetReturnKeyValueTags")?;
+
+        let success = sys::SteamAPI_ISteamUGC_SetReturnMetadata(ugc, handle, self.return_metadata);
+        check_query_function(success, "SetReturnMetadata")?;
+
+        let success = sys::SteamAPI_ISteamUGC_SetReturnChildren(ugc, handle, self.return_children);
+        check_query_function(success, "SetReturnChildren")?;
+
+        let success = sys::SteamAPI_ISteamUGC_SetReturnAdditionalPreviews(
+            ugc,
+            handle,
+            self.return_additional_previews,
+        );
+        check_query_function(success, "SetReturnAdditionalPreviews")?;
+
+        if let Some(days) = self.return_playtime_stats {
+            let success = sys::SteamAPI_ISteamUGC_SetReturnPlaytimeStats(ugc, handle, days);
+            check_query_function(success, "SetReturnPlaytimeStats")?;
+        }
+
+        let success = sys::SteamAPI_ISteamUGC_SetMatchAnyTag(ugc, handle, self.match_any_tag);
+        check_query_function(success, "SetMatchAnyTag")?;
+
+        for (tag, required) in &self.tags {
+            if *required {
+                let success = sys::SteamAPI_ISteamUGC_AddRequiredTag(ugc, handle, tag.as_ptr());
+                check_query_function(success, "AddRequiredTag")?;
+            } else {
+                let success = sys::SteamAPI_ISteamUGC_AddExcludedTag(ugc, handle, tag.as_ptr());
+                check_query_function(success, "AddExcludedTag")?;
+            }
+        }
+
+        for group in &self.tag_groups {
+            let mut array = group.to_param_string_array();
+            let success = sys::SteamAPI_ISteamUGC_AddRequiredTagGroup(ugc, handle, &array.as_raw());
+            check_query_function(success, "AddRequiredTagGroup")?;
+        }
+
+        for (key, value) in &self.key_value_tags {
+            let success = sys::SteamAPI_ISteamUGC_AddRequiredKeyValueTag(
+                ugc,
+                handle,
+                key.as_ptr(),
+                value.as_ptr(),
+            );
+            check_query_function(success, "AddRequiredKeyValueTag")?;
+        }
+
+        if self.return_only_ids {
+            let success = sys::SteamAPI_ISteamUGC_SetReturnOnlyIDs(ugc, handle, true);
+            check_query_function(success, "SetReturnOnlyIDs")?;
+        }
+
+        if self.return_total_only {
+            let success = sys::SteamAPI_ISteamUGC_SetReturnTotalOnly(ugc, handle, true);
+            check_query_function(success, "SetReturnTotalOnly")?;
+        }
+
+        if let Some(language) = &self.language {
+            let success = sys::SteamAPI_ISteamUGC_SetLanguage(ugc, handle, language.as_ptr());
+            check_query_function(success, "SetLanguage")?;
+        }
+
+        if let Some(max_age) = self.allow_cached_response {
+            let max_age_seconds = u32::try_from(max_age.as_secs()).unwrap_or(u32::MAX);
+            let success =
+                sys::SteamAPI_ISteamUGC_SetAllowCachedResponse(ugc, handle, max_age_seconds);
+            check_query_function(success, "SetAllowCachedResponse")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Implements the builder methods that filter which items a query matches, on a query builder
+/// type with a `config: QueryConfig` field.
+macro_rules! impl_query_filter_methods {
+    ($builder:ident) => {
+        impl $builder {
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetMatchAnyTag>
+            pub fn match_any_tags(mut self) -> Self {
+                self.config.match_any_tag = true;
+                self
+            }
+
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetMatchAnyTag>
+            pub fn match_all_tags(mut self) -> Self {
+                self.config.match_any_tag = false;
+                self
+            }
+
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddRequiredTag>
+            pub fn required_tag(mut self, tag: impl Into<Vec<u8>>) -> Self {
+                self.config
+                    .tags
+                    .insert(CString::new(tag).expect("Tag contains nul byte(s)"), true);
+                self
+            }
+
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddRequiredTag>
+            pub fn required_tags<T: Into<Vec<u8>>>(
+                mut self,
+                tags: impl IntoIterator<Item = T>,
+            ) -> Self {
+                let tags = tags
+                    .into_iter()
+                    .map(|tag| (CString::new(tag).expect("Tag contains nul byte(s)"), true));
+                self.config.tags.extend(tags);
+                self
+            }
+
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddExcludedTag>
+            pub fn excluded_tag(mut self, tag: impl Into<Vec<u8>>) -> Self {
+                self.config
+                    .tags
+                    .insert(CString::new(tag).expect("Tag contains nul byte(s)"), false);
+                self
+            }
+
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddExcludedTag>
+            pub fn excluded_tags<T: Into<Vec<u8>>>(
+                mut self,
+                tags: impl IntoIterator<Item = T>,
+            ) -> Self {
+                let tags = tags
+                    .into_iter()
+                    .map(|tag| (CString::new(tag).expect("Tag contains nul byte(s)"), false));
+                self.config.tags.extend(tags);
+                self
+            }
+
+            /// Requires matching items to have at least one of the given tags. Each call adds
+            /// another group, all of which must be satisfied.
+            ///
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddRequiredTagGroup>
+            pub fn required_tag_group(mut self, tags: Tags) -> Self {
+                self.config.tag_groups.push(tags);
+                self
+            }
+
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddRequiredKeyValueTag>
+            pub fn required_key_value_tag(
+                mut self,
+                key: impl Into<Vec<u8>>,
+                value: impl Into<Vec<u8>>,
+            ) -> Self {
+                self.config.key_value_tags.push((
+                    CString::new(key).expect("Key contains nul byte(s)"),
+                    CString::new(value).expect("Value contains nul byte(s)"),
+                ));
+                self
+            }
+
+            /// Limits the number of items the query returns. By default, all matching items are
+            /// returned, fetching as many pages as needed.
+            pub fn max_results(mut self, max_results: u32) -> Self {
+                self.config.max_results = Some(max_results);
+                self
+            }
+        }
+    };
+}
+
+/// Implements the builder methods that control what data is returned for each item, on a query
+/// builder type with a `config: QueryConfig` field.
+macro_rules! impl_query_data_methods {
+    ($builder:ident) => {
+        impl $builder {
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetReturnLongDescription>
+            pub fn return_long_description(mut self) -> Self {
+                self.config.return_long_description = true;
+                self
+            }
+
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetReturnKeyValueTags>
+            pub fn return_key_value_tags(mut self) -> Self {
+                self.config.return_key_value_tags = true;
+                self
+            }
+
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetReturnMetadata>
+            pub fn return_metadata(mut self) -> Self {
+                self.config.return_metadata = true;
+                self
+            }
+
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetReturnChildren>
+            pub fn return_children(mut self) -> Self {
+                self.config.return_children = true;
+                self
+            }
+
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetReturnAdditionalPreviews>
+            pub fn return_additional_previews(mut self) -> Self {
+                self.config.return_additional_previews = true;
+                self
+            }
+
+            /// Returns the playtime statistics of the items over the last `days` days. A value of
+            /// `0` returns the statistics over the lifetime of the items.
+            ///
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetReturnPlaytimeStats>
+            pub fn return_playtime_stats(mut self, days: u32) -> Self {
+                self.config.return_playtime_stats = Some(days);
+                self
+            }
+
+            /// Decodes text fields that aren't valid UTF-8 lossily, replacing invalid sequences with
+            /// `U+FFFD REPLACEMENT CHARACTER`.
+            ///
+            /// By default, items with text fields that aren't valid UTF-8 are returned as errors.
+            pub fn lossy_utf8(mut self) -> Self {
+                self.config.lossy_utf8 = true;
+                self
+            }
+
+            /// Only returns the published file IDs of the matching items; the other fields of the
+            /// returned [`UgcDetails`] are left empty.
+            ///
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetReturnOnlyIDs>
+            pub fn return_only_ids(mut self) -> Self {
+                self.config.return_only_ids = true;
+                self
+            }
+
+            /// Sets the language to return the title and description in, e.g. `"french"`. Defaults
+            /// to the user's language.
+            ///
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetLanguage>
+            pub fn language(mut self, language: impl Into<Vec<u8>>) -> Self {
+                self.config.language =
+                    Some(CString::new(language).expect("Language contains nul byte(s)"));
+                self
+            }
+
+            /// Allows the query to be answered from the cache, as long as the cached results are
+            /// no older than `max_age`. The precision is one second.
+            ///
+            /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetAllowCachedResponse>
+            pub fn allow_cached_response(mut self, max_age: Duration) -> Self {
+                self.config.allow_cached_response = Some(max_age);
+                self
+            }
+        }
+    };
 }
 
 /// A builder for configuring a request to query all UGC.
@@ -248,12 +939,14 @@ pub struct QueryAllUgc {
     matching_ugc_type: MatchingUgcType,
     creator_app_id: Option<AppId>,
     consumer_app_id: Option<AppId>,
-    max_results: Option<u32>,
-    match_any_tag: bool,
-    tags: BTreeMap<CString, bool>,
-    return_long_description: bool,
+    search_text: Option<CString>,
+    ranked_by_trend_days: Option<u32>,
+    config: QueryConfig,
 }
 
+impl_query_filter_methods!(QueryAllUgc);
+impl_query_data_methods!(QueryAllUgc);
+
 impl QueryAllUgc {
     pub fn new(client: Client, matching_ugc_type: MatchingUgcType) -> Self {
         QueryAllUgc {
@@ -262,10 +955,9 @@ impl QueryAllUgc {
             matching_ugc_type,
             creator_app_id: None,
             consumer_app_id: None,
-            max_results: None,
-            match_any_tag: false,
-            tags: BTreeMap::new(),
-            return_long_description: false,
+            search_text: None,
+            ranked_by_trend_days: None,
+            config: QueryConfig::default(),
         }
     }
 
@@ -299,227 +991,76 @@ impl QueryAllUgc {
         }
     }
 
-    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetMatchAnyTag>
-    pub fn match_any_tags(self) -> Self {
+    /// Only matches items containing the given text in their title or description.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetSearchText>
+    pub fn search_text(self, text: impl Into<Vec<u8>>) -> Self {
         QueryAllUgc {
-            match_any_tag: true,
+            search_text: Some(CString::new(text).expect("Search text contains nul byte(s)")),
             ..self
         }
     }
 
-    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetMatchAnyTag>
-    pub fn match_all_tags(self) -> Self {
+    /// Sets the number of days of votes to consider when using the `RankedByTrend` query type.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetRankedByTrendDays>
+    pub fn ranked_by_trend_days(self, days: u32) -> Self {
         QueryAllUgc {
-            match_any_tag: false,
+            ranked_by_trend_days: Some(days),
             ..self
         }
     }
 
-    /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddRequiredTag>
-    pub fn required_tag(mut self, tag: impl Into<Vec<u8>>) -> Self {
-        self.tags
-            .insert(CString::new(tag).expect("Tag contains nul byte(s)"), true);
-        self
-    }
-
-    /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddRequiredTag>
-    pub fn required_tags<T: Into<Vec<u8>>>(mut self, tags: impl IntoIterator<Item = T>) -> Self {
-        let tags = tags
-            .into_iter()
-            .map(|tag| (CString::new(tag).expect("Tag contains nul byte(s)"), true));
-        self.tags.extend(tags);
-        self
-    }
-
-    /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddExcludedTag>
-    pub fn excluded_tag(mut self, tag: impl Into<Vec<u8>>) -> Self {
-        self.tags
-            .insert(CString::new(tag).expect("Tag contains nul byte(s)"), false);
-        self
-    }
+    /// Executes the query, only fetching the total number of matching items.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetReturnTotalOnly>
+    pub async fn total_results(mut self) -> Result<u32, QueryAllUgcError> {
+        self.config.return_total_only = true;
 
-    /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddExcludedTag>
-    pub fn excluded_tags<T: Into<Vec<u8>>>(mut self, tags: impl IntoIterator<Item = T>) -> Self {
-        let tags = tags
-            .into_iter()
-            .map(|tag| (CString::new(tag).expect("Tag contains nul byte(s)"), false));
-        self.tags.extend(tags);
-        self
-    }
+        let current_app_id = self.check_app_ids()?;
+        let query = unsafe { self.create_query(current_app_id, ptr::null()) }?;
+        let response = send_query(&query).await.map_err(|steam_result| {
+            query_all_ugc_error::SendQueryUGCRequestSnafu { steam_result }.build()
+        })?;
 
-    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetReturnLongDescription>
-    pub fn return_long_description(self) -> Self {
-        QueryAllUgc {
-            return_long_description: true,
-            ..self
-        }
+        Ok(response.m_unTotalMatchingResults)
     }
 
     /// Executes the query.
     pub fn run(self) -> impl Stream<Item = Result<UgcDetails, QueryAllUgcError>> + Send {
         Gen::new(|co| async move {
-            let current_app_id = self.client.app_id();
-            if let (Some(x), Some(y)) = (self.creator_app_id, self.consumer_app_id) {
-                if x != current_app_id && y != current_app_id {
-                    co.yield_(AppIdSnafu.fail()).await;
+            let current_app_id = match self.check_app_ids() {
+                Ok(x) => x,
+                Err(e) => {
+                    co.yield_(Err(e)).await;
+                    return;
                 }
-            }
+            };
 
-            let max_results = self.max_results.unwrap_or(u32::MAX);
+            let max_results = self.config.max_results.unwrap_or(u32::MAX);
 
-            let client = self.client.clone();
             let mut cursor: Option<Vec<c_char>> = None;
             let mut details_returned = 0;
             loop {
-                let handle = unsafe {
-                    let pointer = match &cursor {
-                        Some(x) => x.as_ptr(),
-                        None => ptr::null(),
-                    };
-                    sys::SteamAPI_ISteamUGC_CreateQueryAllUGCRequestCursor(
-                        *client.0.ugc,
-                        self.query_type.into(),
-                        self.matching_ugc_type.into(),
-                        self.creator_app_id.unwrap_or(current_app_id).into(),
-                        self.consumer_app_id.unwrap_or(current_app_id).into(),
-                        pointer,
-                    )
-                };
-                if handle == sys::k_UGCQueryHandleInvalid {
-                    co.yield_(CreateQueryAllUGCRequestSnafu.fail()).await;
-                    break;
-                }
-
-                unsafe {
-                    let success = sys::SteamAPI_ISteamUGC_SetReturnLongDescription(
-                        *client.0.ugc,
-                        handle,
-                        self.return_long_description,
-                    );
-                    assert!(success, "SetReturnLongDescription failed");
-
-                    let success = sys::SteamAPI_ISteamUGC_SetMatchAnyTag(
-                        *client.0.ugc,
-                        handle,
-                        self.match_any_tag,
-                    );
-                    assert!(success, "SetMatchAnyTag failed");
-
-                    for (tag, required) in &self.tags {
-                        if *required {
-                            sys::SteamAPI_ISteamUGC_AddRequiredTag(
-                                *client.0.ugc,
-                                handle,
-                                tag.as_ptr(),
-                            );
-                        } else {
-                            sys::SteamAPI_ISteamUGC_AddExcludedTag(
-                                *client.0.ugc,
-                                handle,
-                                tag.as_ptr(),
-                            );
-                        }
-                    }
-                }
-
-                let response: sys::SteamUGCQueryCompleted_t = unsafe {
-                    let handle = sys::SteamAPI_ISteamUGC_SendQueryUGCRequest(*client.0.ugc, handle);
-
-                    self.client.register_for_call_result(handle).await
+                let pointer = match &cursor {
+                    Some(x) => x.as_ptr(),
+                    None => ptr::null(),
                 };
-
-                {
-                    let result = SteamResult::from_inner(response.m_eResult);
-                    if result != SteamResult::OK {
-                        co.yield_(
-                            SendQueryUGCRequestSnafu {
-                                steam_result: result,
-                            }
-                            .fail(),
-                        )
-                        .await;
+                let query = match unsafe { self.create_query(current_app_id, pointer) } {
+                    Ok(x) => x,
+                    Err(e) => {
+                        co.yield_(Err(e)).await;
                         break;
                     }
-                }
+                };
 
                 let items_to_reach_quota = max_results - details_returned;
-                for i in 0..cmp::min(items_to_reach_quota, response.m_unNumResultsReturned) {
-                    let mut details: MaybeUninit<sys::SteamUGCDetails_t> = MaybeUninit::uninit();
-                    let success = unsafe {
-                        sys::SteamAPI_ISteamUGC_GetQueryUGCResult(
-                            *client.0.ugc,
-                            response.m_handle,
-                            i,
-                            details.as_mut_ptr(),
-                        )
+                let response =
+                    match yield_query_page(&co, query, &self.config, items_to_reach_quota).await {
+                        Some(x) => x,
+                        None => break,
                     };
-                    assert!(success, "GetQueryUGCResult failed");
-                    let details = unsafe { details.assume_init() };
-                    let preview_url = unsafe {
-                        let mut buf = vec![0_u8; 256];
-                        sys::SteamAPI_ISteamUGC_GetQueryUGCPreviewURL(
-                            *client.0.ugc,
-                            response.m_handle,
-                            i,
-                            buf.as_mut_ptr() as *mut c_char,
-                            u32::try_from(buf.len()).unwrap(),
-                        );
-                        String::from_utf8_nul_truncating(buf)
-                            .expect("Workshop item's preview image URL is not valid UTF-8")
-                    };
-                    let details = UgcDetails {
-                        published_file_id: PublishedFileId(details.m_nPublishedFileId),
-                        file_type: WorkshopFileType::from_inner(details.m_eFileType),
-                        creator_app_id: AppId(details.m_nCreatorAppID),
-                        title: String::from_utf8_nul_truncating(&details.m_rgchTitle[..])
-                            .expect("Workshop item's title is not valid UTF-8"),
-                        description: String::from_utf8_nul_truncating(
-                            &details.m_rgchDescription[..],
-                        )
-                        .expect("Workshop item's description is not valid UTF-8"),
-                        steam_id_owner: details.m_ulSteamIDOwner.into(),
-                        time_created: Utc
-                            .timestamp_opt(i64::from(details.m_rtimeCreated), 0)
-                            .unwrap(),
-                        time_updated: Utc
-                            .timestamp_opt(i64::from(details.m_rtimeUpdated), 0)
-                            .unwrap(),
-                        time_added_to_user_list: if details.m_rtimeAddedToUserList == 0 {
-                            None
-                        } else {
-                            Some(
-                                Utc.timestamp_opt(i64::from(details.m_rtimeAddedToUserList), 0)
-                                    .unwrap(),
-                            )
-                        },
-                        visibility: PublishedFileVisibility::from_inner(details.m_eVisibility),
-                        banned: details.m_bBanned,
-                        accepted_for_use: details.m_bAcceptedForUse,
-                        tags_truncated: details.m_bTagsTruncated,
-                        tags: Tags(
-                            String::from_utf8_nul_truncating(&details.m_rgchTags[..])
-                                .expect("Workshop item's tags are not valid UTF-8"),
-                        ),
-                        file: UgcHandle::from_inner(details.m_hFile),
-                        preview_file: UgcHandle::from_inner(details.m_hPreviewFile),
-                        preview_url,
-                        file_name: String::from_utf8_nul_truncating(&details.m_pchFileName[..])
-                            .expect("Workshop item's file name is not valid UTF-8"),
-                        file_size: details.m_nFileSize,
-                        preview_file_size: details.m_nPreviewFileSize,
-                        url: String::from_utf8_nul_truncating(&details.m_rgchURL[..])
-                            .expect("Workshop item's url is not valid UTF-8"),
-                        votes_up: details.m_unVotesUp,
-                        votes_down: details.m_unVotesDown,
-                        score: details.m_flScore,
-                        num_children: details.m_unNumChildren,
-                    };
-
-                    co.yield_(Ok(details)).await;
-                    details_returned += 1;
-                }
-
-                unsafe { sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(*client.0.ugc, handle) };
+                details_returned += cmp::min(items_to_reach_quota, response.m_unNumResultsReturned);
 
                 let more_items_wanted = items_to_reach_quota > 0;
                 let more_items_available = response.m_unTotalMatchingResults > details_returned;
@@ -537,4 +1078,1641 @@ impl QueryAllUgc {
             }
         })
     }
+
+    /// Returns the current application's App ID, after checking that it is either the creator or
+    /// the consumer App ID of the query.
+    fn check_app_ids(&self) -> Result<AppId, QueryAllUgcError> {
+        let current_app_id = self.client.app_id();
+        if let (Some(x), Some(y)) = (self.creator_app_id, self.consumer_app_id) {
+            ensure!(
+                x == current_app_id || y == current_app_id,
+                query_all_ugc_error::AppIdSnafu
+            );
+        }
+
+        Ok(current_app_id)
+    }
+
+    /// Creates and configures a query handle starting at `cursor`, which may be null to start at
+    /// the first page.
+    ///
+    /// # Safety
+    ///
+    /// `cursor` must be null or point to a nul-terminated cursor returned by a previous query.
+    unsafe fn create_query(
+        &self,
+        current_app_id: AppId,
+        cursor: *const c_char,
+    ) -> Result<QueryHandle, QueryAllUgcError> {
+        let ugc = *self.client.0.ugc;
+        let handle = sys::SteamAPI_ISteamUGC_CreateQueryAllUGCRequestCursor(
+            ugc,
+            self.query_type.into(),
+            self.matching_ugc_type.into(),
+            self.creator_app_id.unwrap_or(current_app_id).into(),
+            self.consumer_app_id.unwrap_or(current_app_id).into(),
+            cursor,
+        );
+        ensure!(
+            handle != sys::k_UGCQueryHandleInvalid,
+            query_all_ugc_error::CreateQueryAllUGCRequestSnafu
+        );
+        let query = QueryHandle {
+            client: self.client.clone(),
+            handle,
+        };
+
+        let configured = self.config.apply(ugc, handle).and_then(|()| {
+            if let Some(text) = &self.search_text {
+                let success = sys::SteamAPI_ISteamUGC_SetSearchText(ugc, handle, text.as_ptr());
+                check_query_function(success, "SetSearchText")?;
+            }
+
+            if let Some(days) = self.ranked_by_trend_days {
+                let success = sys::SteamAPI_ISteamUGC_SetRankedByTrendDays(ugc, handle, days);
+                check_query_function(success, "SetRankedByTrendDays")?;
+            }
+
+            Ok(())
+        });
+        configured.map_err(|e| {
+            query_all_ugc_error::ConfigureQuerySnafu {
+                function: e.function,
+            }
+            .build()
+        })?;
+
+        Ok(query)
+    }
+}
+
+/// A builder for configuring a request to query the UGC associated with a user, such as the items
+/// they have published, subscribed to, or favorited.
+///
+/// See <https://partner.steamgames.com/doc/api/ISteamUGC#CreateQueryUserUGCRequest>.
+///
+/// # Example
+///
+/// ```no_run
+/// # let client: steamworks::Client = unimplemented!();
+/// use steamworks::ugc::{MatchingUgcType, UserUgcList, UserUgcListSortOrder};
+///
+/// let my_subscriptions = client
+///     .query_user_ugc(
+///         client.steam_id().account_id(),
+///         UserUgcList::Subscribed,
+///         MatchingUgcType::Items,
+///         UserUgcListSortOrder::SubscriptionDateDesc,
+///     )
+///     .run();
+/// ```
+#[derive(Debug, Clone)]
+pub struct QueryUserUgc {
+    client: Client,
+    account_id: AccountId,
+    list_type: UserUgcList,
+    matching_ugc_type: MatchingUgcType,
+    sort_order: UserUgcListSortOrder,
+    creator_app_id: Option<AppId>,
+    consumer_app_id: Option<AppId>,
+    cloud_file_name_filter: Option<CString>,
+    config: QueryConfig,
+}
+
+impl_query_filter_methods!(QueryUserUgc);
+impl_query_data_methods!(QueryUserUgc);
+
+impl QueryUserUgc {
+    pub fn new(
+        client: Client,
+        account_id: AccountId,
+        list_type: UserUgcList,
+        matching_ugc_type: MatchingUgcType,
+        sort_order: UserUgcListSortOrder,
+    ) -> Self {
+        QueryUserUgc {
+            client,
+            account_id,
+            list_type,
+            matching_ugc_type,
+            sort_order,
+            creator_app_id: None,
+            consumer_app_id: None,
+            cloud_file_name_filter: None,
+            config: QueryConfig::default(),
+        }
+    }
+
+    /// Sets the nCreatorAppID argument of
+    /// [CreateQueryUserUGCRequest](https://partner.steamgames.com/doc/api/ISteamUGC#CreateQueryUserUGCRequest)
+    ///
+    /// Defaults to the current application's App ID.
+    pub fn creator_app_id(self, app_id: AppId) -> Self {
+        QueryUserUgc {
+            creator_app_id: Some(app_id),
+            ..self
+        }
+    }
+
+    /// Sets the nConsumerAppID argument of
+    /// [CreateQueryUserUGCRequest](https://partner.steamgames.com/doc/api/ISteamUGC#CreateQueryUserUGCRequest)
+    ///
+    /// Defaults to the current application's App ID.
+    pub fn consumer_app_id(self, app_id: AppId) -> Self {
+        QueryUserUgc {
+            consumer_app_id: Some(app_id),
+            ..self
+        }
+    }
+
+    /// Only matches items whose file has the given cloud file name.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetCloudFileNameFilter>
+    pub fn cloud_file_name_filter(self, file_name: impl Into<Vec<u8>>) -> Self {
+        QueryUserUgc {
+            cloud_file_name_filter: Some(
+                CString::new(file_name).expect("File name contains nul byte(s)"),
+            ),
+            ..self
+        }
+    }
+
+    /// Executes the query.
+    pub fn run(self) -> impl Stream<Item = Result<UgcDetails, QueryUserUgcError>> + Send {
+        Gen::new(|co| async move {
+            let current_app_id = match self.check_app_ids() {
+                Ok(x) => x,
+                Err(e) => {
+                    co.yield_(Err(e)).await;
+                    return;
+                }
+            };
+
+            let max_results = self.config.max_results.unwrap_or(u32::MAX);
+
+            let mut page = 1;
+            let mut details_returned = 0;
+            loop {
+                let query = match unsafe { self.create_query(current_app_id, page) } {
+                    Ok(x) => x,
+                    Err(e) => {
+                        co.yield_(Err(e)).await;
+                        break;
+                    }
+                };
+
+                let items_to_reach_quota = max_results - details_returned;
+                let response =
+                    match yield_query_page(&co, query, &self.config, items_to_reach_quota).await {
+                        Some(x) => x,
+                        None => break,
+                    };
+                details_returned += cmp::min(items_to_reach_quota, response.m_unNumResultsReturned);
+
+                let more_items_wanted = items_to_reach_quota > 0;
+                let more_items_available = response.m_unNumResultsReturned > 0
+                    && response.m_unTotalMatchingResults > details_returned;
+                if !more_items_wanted || !more_items_available {
+                    break;
+                }
+
+                page += 1;
+            }
+        })
+    }
+
+    /// Returns the current application's App ID, after checking that it is either the creator or
+    /// the consumer App ID of the query.
+    fn check_app_ids(&self) -> Result<AppId, QueryUserUgcError> {
+        let current_app_id = self.client.app_id();
+        if let (Some(x), Some(y)) = (self.creator_app_id, self.consumer_app_id) {
+            ensure!(
+                x == current_app_id || y == current_app_id,
+                query_user_ugc_error::AppIdSnafu
+            );
+        }
+
+        Ok(current_app_id)
+    }
+
+    /// Creates and configures a query handle for the given page, starting at 1.
+    unsafe fn create_query(
+        &self,
+        current_app_id: AppId,
+        page: u32,
+    ) -> Result<QueryHandle, QueryUserUgcError> {
+        let ugc = *self.client.0.ugc;
+        let handle = sys::SteamAPI_ISteamUGC_CreateQueryUserUGCRequest(
+            ugc,
+            self.account_id.into(),
+            self.list_type.into(),
+            self.matching_ugc_type.into(),
+            self.sort_order.into(),
+            self.creator_app_id.unwrap_or(current_app_id).into(),
+            self.consumer_app_id.unwrap_or(current_app_id).into(),
+            page,
+        );
+        ensure!(
+            handle != sys::k_UGCQueryHandleInvalid,
+            query_user_ugc_error::CreateQueryUserUGCRequestSnafu
+        );
+        let query = QueryHandle {
+            client: self.client.clone(),
+            handle,
+        };
+
+        let configured = self.config.apply(ugc, handle).and_then(|()| {
+            if let Some(file_name) = &self.cloud_file_name_filter {
+                let success =
+                    sys::SteamAPI_ISteamUGC_SetCloudFileNameFilter(ugc, handle, file_name.as_ptr());
+                check_query_function(success, "SetCloudFileNameFilter")?;
+            }
+
+            Ok(())
+        });
+        configured.map_err(|e| {
+            query_user_ugc_error::ConfigureQuerySnafu {
+                function: e.function,
+            }
+            .build()
+        })?;
+
+        Ok(query)
+    }
+}
+
+/// A builder for configuring a request to query the details of specific workshop items.
+///
+/// See <https://partner.steamgames.com/doc/api/ISteamUGC#CreateQueryUGCDetailsRequest>.
+///
+/// # Example
+///
+/// ```no_run
+/// # let client: steamworks::Client = unimplemented!();
+/// use steamworks::ugc::PublishedFileId;
+///
+/// let details = client
+///     .query_ugc_details(&[PublishedFileId(1234), PublishedFileId(5678)])
+///     .return_long_description()
+///     .run();
+/// ```
+#[derive(Debug, Clone)]
+pub struct QueryUgcDetails {
+    client: Client,
+    published_file_ids: Vec<PublishedFileId>,
+    config: QueryConfig,
+}
+
+impl_query_data_methods!(QueryUgcDetails);
+
+impl QueryUgcDetails {
+    pub fn new(client: Client, published_file_ids: impl Into<Vec<PublishedFileId>>) -> Self {
+        QueryUgcDetails {
+            client,
+            published_file_ids: published_file_ids.into(),
+            config: QueryConfig::default(),
+        }
+    }
+
+    /// Executes the query.
+    ///
+    /// The returned details are in the same order as the requested IDs. Items which couldn't be
+    /// retrieved, for example because they were deleted or made private, are returned as errors in
+    /// their place. Large numbers of IDs are split into multiple requests to stay under the
+    /// per-request limit.
+    pub async fn run(self) -> Result<Vec<Result<UgcDetails, UgcItemError>>, QueryUgcDetailsError> {
+        let client = &self.client;
+        let mut found: HashMap<PublishedFileId, Result<UgcDetails, UgcItemError>> =
+            HashMap::with_capacity(self.published_file_ids.len());
+        for chunk in self
+            .published_file_ids
+            .chunks(sys::kNumUGCResultsPerPage as usize)
+        {
+            let mut ids: Vec<sys::PublishedFileId_t> = chunk.iter().map(|x| x.0).collect();
+            let handle = unsafe {
+                sys::SteamAPI_ISteamUGC_CreateQueryUGCDetailsRequest(
+                    *client.0.ugc,
+                    ids.as_mut_ptr(),
+                    u32::try_from(ids.len()).unwrap(),
+                )
+            };
+            ensure!(
+                handle != sys::k_UGCQueryHandleInvalid,
+                query_ugc_details_error::CreateQueryUGCDetailsRequestSnafu
+            );
+            let query = QueryHandle {
+                client: client.clone(),
+                handle,
+            };
+
+            unsafe { self.config.apply(*client.0.ugc, handle) }.map_err(|e| {
+                query_ugc_details_error::ConfigureQuerySnafu {
+                    function: e.function,
+                }
+                .build()
+            })?;
+
+            let response = send_query(&query).await.map_err(|steam_result| {
+                query_ugc_details_error::SendQueryUGCRequestSnafu { steam_result }.build()
+            })?;
+
+            for i in 0..response.m_unNumResultsReturned {
+                let raw =
+                    unsafe { raw_query_result(client, response.m_handle, i) }.map_err(|e| {
+                        query_ugc_details_error::ReadQueryResultSnafu {
+                            function: e.function,
+                        }
+                        .build()
+                    })?;
+                let result = SteamResult::from_inner_or_fail(raw.m_eResult);
+                let published_file_id = PublishedFileId(raw.m_nPublishedFileId);
+                let details = if result == SteamResult::OK {
+                    match unsafe {
+                        query_result_details(client, response.m_handle, i, &raw, &self.config)
+                    } {
+                        Ok(details) => Ok(details),
+                        Err(QueryResultError::InvalidUtf8(e)) => ugc_item_error::InvalidUtf8Snafu {
+                            published_file_id,
+                            field: e.field,
+                        }
+                        .fail(),
+                        Err(QueryResultError::Function(e)) => {
+                            return query_ugc_details_error::ReadQueryResultSnafu {
+                                function: e.function,
+                            }
+                            .fail();
+                        }
+                    }
+                } else {
+                    ugc_item_error::UnavailableSnafu {
+                        published_file_id,
+                        steam_result: result,
+                    }
+                    .fail()
+                };
+                found.insert(published_file_id, details);
+            }
+        }
+
+        let details = self
+            .published_file_ids
+            .iter()
+            .map(|&published_file_id| match found.get(&published_file_id) {
+                Some(result) => result.clone(),
+                None => ugc_item_error::UnavailableSnafu {
+                    published_file_id,
+                    steam_result: SteamResult::FileNotFound,
+                }
+                .fail(),
+            })
+            .collect();
+
+        Ok(details)
+    }
+}
+
+/// A builder for configuring an update to a workshop item, which is uploaded by calling
+/// [`submit`](Self::submit).
+///
+/// Only the properties that are set are changed; everything else keeps its current value. See
+/// <https://partner.steamgames.com/doc/features/workshop/implementation#uploading_a_workshop_item>.
+///
+/// # Example
+///
+/// ```no_run
+/// # let client: steamworks::Client = unimplemented!();
+/// # let published_file_id: steamworks::ugc::PublishedFileId = unimplemented!();
+/// use steamworks::ugc::PublishedFileVisibility;
+///
+/// let update = client
+///     .start_item_update(client.app_id(), published_file_id)
+///     .title("My Level")
+///     .visibility(PublishedFileVisibility::Public)
+///     .content_folder("/path/to/my_level")
+///     .change_note("Fixed the second jump")
+///     .submit();
+/// ```
+#[derive(Debug)]
+pub struct ItemUpdate {
+    client: Client,
+    consumer_app_id: AppId,
+    published_file_id: PublishedFileId,
+    /// Set by [`submit`](Self::submit) once the update has been started, and shared with the
+    /// progress streams, which end once it has been dropped
+    handle: Arc<AtomicU64>,
+    title: Option<CString>,
+    description: Option<CString>,
+    language: Option<CString>,
+    metadata: Option<CString>,
+    visibility: Option<PublishedFileVisibility>,
+    tags: Option<Tags>,
+    content_folder: Option<CString>,
+    preview_file: Option<CString>,
+    removed_key_value_tags: Vec<CString>,
+    added_key_value_tags: Vec<(CString, CString)>,
+    removed_previews: Vec<u32>,
+    added_preview_files: Vec<(CString, ItemPreviewType)>,
+    added_preview_videos: Vec<CString>,
+    change_note: Option<CString>,
+}
+
+impl ItemUpdate {
+    pub(crate) fn new(
+        client: Client,
+        consumer_app_id: AppId,
+        published_file_id: PublishedFileId,
+    ) -> Self {
+        ItemUpdate {
+            client,
+            consumer_app_id,
+            published_file_id,
+            handle: Arc::new(AtomicU64::new(sys::k_UGCUpdateHandleInvalid)),
+            title: None,
+            description: None,
+            language: None,
+            metadata: None,
+            visibility: None,
+            tags: None,
+            content_folder: None,
+            preview_file: None,
+            removed_key_value_tags: Vec::new(),
+            added_key_value_tags: Vec::new(),
+            removed_previews: Vec::new(),
+            added_preview_files: Vec::new(),
+            added_preview_videos: Vec::new(),
+            change_note: None,
+        }
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetItemTitle>
+    pub fn title(self, title: impl Into<Vec<u8>>) -> Self {
+        ItemUpdate {
+            title: Some(CString::new(title).expect("Title contains nul byte(s)")),
+            ..self
+        }
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetItemDescription>
+    pub fn description(self, description: impl Into<Vec<u8>>) -> Self {
+        ItemUpdate {
+            description: Some(CString::new(description).expect("Description contains nul byte(s)")),
+            ..self
+        }
+    }
+
+    /// Sets the language of the title and description being set, e.g. `"french"`. Defaults to
+    /// `"english"`.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetItemUpdateLanguage>
+    pub fn language(self, language: impl Into<Vec<u8>>) -> Self {
+        ItemUpdate {
+            language: Some(CString::new(language).expect("Language contains nul byte(s)")),
+            ..self
+        }
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetItemMetadata>
+    pub fn metadata(self, metadata: impl Into<Vec<u8>>) -> Self {
+        ItemUpdate {
+            metadata: Some(CString::new(metadata).expect("Metadata contains nul byte(s)")),
+            ..self
+        }
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetItemVisibility>
+    pub fn visibility(self, visibility: PublishedFileVisibility) -> Self {
+        ItemUpdate {
+            visibility: Some(visibility),
+            ..self
+        }
+    }
+
+    /// Replaces the item's tags.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetItemTags>
+    pub fn tags(self, tags: Tags) -> Self {
+        ItemUpdate {
+            tags: Some(tags),
+            ..self
+        }
+    }
+
+    /// Sets the folder whose contents are uploaded as the item's content.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetItemContent>
+    pub fn content_folder(self, path: impl Into<Vec<u8>>) -> Self {
+        ItemUpdate {
+            content_folder: Some(CString::new(path).expect("Path contains nul byte(s)")),
+            ..self
+        }
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SetItemPreview>
+    pub fn preview_file(self, path: impl Into<Vec<u8>>) -> Self {
+        ItemUpdate {
+            preview_file: Some(CString::new(path).expect("Path contains nul byte(s)")),
+            ..self
+        }
+    }
+
+    /// Removes all key-value tags with the given key.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#RemoveItemKeyValueTags>
+    pub fn remove_key_value_tags(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.removed_key_value_tags
+            .push(CString::new(key).expect("Key contains nul byte(s)"));
+        self
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddItemKeyValueTag>
+    pub fn add_key_value_tag(mut self, key: impl Into<Vec<u8>>, value: impl Into<Vec<u8>>) -> Self {
+        self.added_key_value_tags.push((
+            CString::new(key).expect("Key contains nul byte(s)"),
+            CString::new(value).expect("Value contains nul byte(s)"),
+        ));
+        self
+    }
+
+    /// Removes the additional preview at `index`, as returned in
+    /// [`UgcDetails::additional_previews`].
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#RemoveItemPreview>
+    pub fn remove_preview(mut self, index: u32) -> Self {
+        self.removed_previews.push(index);
+        self
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddItemPreviewFile>
+    pub fn add_preview_file(
+        mut self,
+        path: impl Into<Vec<u8>>,
+        preview_type: ItemPreviewType,
+    ) -> Self {
+        self.added_preview_files.push((
+            CString::new(path).expect("Path contains nul byte(s)"),
+            preview_type,
+        ));
+        self
+    }
+
+    /// Adds a YouTube video, given its video ID, as an additional preview.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#AddItemPreviewVideo>
+    pub fn add_preview_video(mut self, video_id: impl Into<Vec<u8>>) -> Self {
+        self.added_preview_videos
+            .push(CString::new(video_id).expect("Video ID contains nul byte(s)"));
+        self
+    }
+
+    /// Sets the change note shown in the item's change log.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#SubmitItemUpdate>
+    pub fn change_note(self, change_note: impl Into<Vec<u8>>) -> Self {
+        ItemUpdate {
+            change_note: Some(CString::new(change_note).expect("Change note contains nul byte(s)")),
+            ..self
+        }
+    }
+
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#GetItemUpdateProgress>
+    ///
+    /// Returns `None` if the update is not being processed.
+    pub fn progress(&self) -> Option<ItemUpdateProgress> {
+        item_update_progress(&self.client, self.handle.load(Ordering::Acquire))
+    }
+
+    /// Polls [`progress`](Self::progress) every `interval`, yielding the progress whenever it's
+    /// available.
+    ///
+    /// The stream is created before the update is submitted, and ends once the submit future
+    /// finishes or is dropped, or once the update is dropped without being submitted.
+    pub fn progress_updates(
+        &self,
+        interval: Duration,
+    ) -> impl Stream<Item = ItemUpdateProgress> + Send {
+        let client = self.client.clone();
+        let handle = Arc::downgrade(&self.handle);
+        Gen::new(|co| async move {
+            // The handle is only upgraded while reading the progress, so that it isn't kept alive
+            // while waiting
+            while let Some(progress) = handle
+                .upgrade()
+                .map(|handle| item_update_progress(&client, handle.load(Ordering::Acquire)))
+            {
+                if let Some(progress) = progress {
+                    co.yield_(progress).await;
+                }
+
+                client.delay(interval).await;
+            }
+        })
+    }
+
+    /// Starts and uploads the update.
+    ///
+    /// Fails without uploading anything if Steam couldn't start the update, for example because
+    /// the App ID is invalid.
+    ///
+    /// <https://partner.steamgames.com/doc/api/ISteamUGC#StartItemUpdate>
+    pub async fn submit(self) -> Result<SubmittedItemUpdate, SubmitItemUpdateError> {
+        let handle = unsafe {
+            sys::SteamAPI_ISteamUGC_StartItemUpdate(
+                *self.client.0.ugc,
+                self.consumer_app_id.into(),
+                self.published_file_id.into(),
+            )
+        };
+        ensure!(
+            handle != sys::k_UGCUpdateHandleInvalid,
+            submit_item_update_error::StartItemUpdateSnafu
+        );
+        self.handle.store(handle, Ordering::Release);
+        unsafe { self.apply(handle) }?;
+
+        let response: sys::SubmitItemUpdateResult_t = unsafe {
+            let handle = sys::SteamAPI_ISteamUGC_SubmitItemUpdate(
+                *self.client.0.ugc,
+                handle,
+                self.change_note
+                    .as_ref()
+                    .map(|x| x.as_ptr())
+                    .unwrap_or(ptr::null()),
+            );
+
+            self.client.register_for_call_result(handle).await
+        };
+
+        let steam_result = SteamResult::from_inner(response.m_eResult);
+        ensure!(
+            steam_result == SteamResult::OK,
+            submit_item_update_error::SubmitItemUpdateSnafu { steam_result }
+        );
+
+        Ok(SubmittedItemUpdate {
+            published_file_id: PublishedFileId(response.m_nPublishedFileId),
+            user_needs_to_accept_workshop_legal_agreement: response
+                .m_bUserNeedsToAcceptWorkshopLegalAgreement,
+        })
+    }
+
+    unsafe fn apply(&self, handle: sys::UGCUpdateHandle_t) -> Result<(), SubmitItemUpdateError> {
+        fn check(success: bool, function: &'static str) -> Result<(), SubmitItemUpdateError> {
+            ensure!(
+                success,
+                submit_item_update_error::SetItemPropertySnafu { function }
+            );
+            Ok(())
+        }
+
+        let ugc = *self.client.0.ugc;
+
+        if let Some(language) = &self.language {
+            let success =
+                sys::SteamAPI_ISteamUGC_SetItemUpdateLanguage(ugc, handle, language.as_ptr());
+            check(success, "SetItemUpdateLanguage")?;
+        }
+
+        if let Some(title) = &self.title {
+            let success = sys::SteamAPI_ISteamUGC_SetItemTitle(ugc, handle, title.as_ptr());
+            check(success, "SetItemTitle")?;
+        }
+
+        if let Some(description) = &self.description {
+            let success =
+                sys::SteamAPI_ISteamUGC_SetItemDescription(ugc, handle, description.as_ptr());
+            check(success, "SetItemDescription")?;
+        }
+
+        if let Some(metadata) = &self.metadata {
+            let success = sys::SteamAPI_ISteamUGC_SetItemMetadata(ugc, handle, metadata.as_ptr());
+            check(success, "SetItemMetadata")?;
+        }
+
+        if let Some(visibility) = self.visibility {
+            let success = sys::SteamAPI_ISteamUGC_SetItemVisibility(ugc, handle, visibility.into());
+            check(success, "SetItemVisibility")?;
+        }
+
+        if let Some(tags) = &self.tags {
+            let mut array = tags.to_param_string_array();
+            let success = sys::SteamAPI_ISteamUGC_SetItemTags(ugc, handle, &array.as_raw());
+            check(success, "SetItemTags")?;
+        }
+
+        if let Some(path) = &self.content_folder {
+            let success = sys::SteamAPI_ISteamUGC_SetItemContent(ugc, handle, path.as_ptr());
+            check(success, "SetItemContent")?;
+        }
+
+        if let Some(path) = &self.preview_file {
+            let success = sys::SteamAPI_ISteamUGC_SetItemPreview(ugc, handle, path.as_ptr());
+            check(success, "SetItemPreview")?;
+        }
+
+        for key in &self.removed_key_value_tags {
+            let success = sys::SteamAPI_ISteamUGC_RemoveItemKeyValueTags(ugc, handle, key.as_ptr());
+            check(success, "RemoveItemKeyValueTags")?;
+        }
+
+        for (key, value) in &self.added_key_value_tags {
+            let success = sys::SteamAPI_ISteamUGC_AddItemKeyValueTag(
+                ugc,
+                handle,
+                key.as_ptr(),
+                value.as_ptr(),
+            );
+            check(success, "AddItemKeyValueTag")?;
+        }
+
+        for &index in &self.removed_previews {
+            let success = sys::SteamAPI_ISteamUGC_RemoveItemPreview(ugc, handle, index);
+            check(success, "RemoveItemPreview")?;
+        }
+
+        for (path, preview_type) in &self.added_preview_files {
+            let success = sys::SteamAPI_ISteamUGC_AddItemPreviewFile(
+                ugc,
+                handle,
+                path.as_ptr(),
+                (*preview_type).into(),
+            );
+            check(success, "AddItemPreviewFile")?;
+        }
+
+        for video_id in &self.added_preview_videos {
+            let success =
+                sys::SteamAPI_ISteamUGC_AddItemPreviewVideo(ugc, handle, video_id.as_ptr());
+            check(success, "AddItemPreviewVideo")?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) async fn create_item(
+    client: &Client,
+    consumer_app_id: AppId,
+    file_type: WorkshopFileType,
+) -> Result<CreatedItem, CreateItemError> {
+    let response: sys::CreateItemResult_t = unsafe {
+        let handle = sys::SteamAPI_ISteamUGC_CreateItem(
+            *client.0.ugc,
+            consumer_app_id.into(),
+            file_type.into(),
+        );
+
+        client.register_for_call_result(handle).await
+    };
+
+    let steam_result = SteamResult::from_inner(response.m_eResult);
+    ensure!(
+        steam_result == SteamResult::OK,
+        create_item_error::CreateItemSnafu { steam_result }
+    );
+
+    Ok(CreatedItem {
+        published_file_id: PublishedFileId(response.m_nPublishedFileId),
+        user_needs_to_accept_workshop_legal_agreement: response
+            .m_bUserNeedsToAcceptWorkshopLegalAgreement,
+    })
+}
+
+/// A call result that reports its outcome as an `EResult`.
+trait UgcCallResult: Copy {
+    fn steam_result(&self) -> SteamResult;
+}
+
+macro_rules! impl_ugc_call_result {
+    ($($call_result:ty),* $(,)?) => {
+        $(
+            impl UgcCallResult for $call_result {
+                fn steam_result(&self) -> SteamResult {
+                    SteamResult::from_inner(self.m_eResult)
+                }
+            }
+        )*
+    };
+}
+
+impl_ugc_call_result!(
+    sys::RemoteStorageSubscribePublishedFileResult_t,
+    sys::RemoteStorageUnsubscribePublishedFileResult_t,
+    sys::SetUserItemVoteResult_t,
+    sys::GetUserItemVoteResult_t,
+    sys::UserFavoriteItemsListChanged_t,
+    sys::AddUGCDependencyResult_t,
+    sys::RemoveUGCDependencyResult_t,
+    sys::AddAppDependencyResult_t,
+    sys::RemoveAppDependencyResult_t,
+    sys::DeleteItemResult_t,
+    sys::StartPlaytimeTrackingResult_t,
+    sys::StopPlaytimeTrackingResult_t,
+);
+
+/// Waits for the result of the call `handle` made to `function`, failing if it reports an error.
+///
+/// # Safety
+///
+/// `T` must be the call result type of `function`.
+async unsafe fn ugc_call_result<T: UgcCallResult>(
+    client: &Client,
+    handle: sys::SteamAPICall_t,
+    function: &'static str,
+) -> Result<T, UgcCallError> {
+    let response: T = client.register_for_call_result(handle).await;
+    let steam_result = response.steam_result();
+    ensure!(
+        steam_result == SteamResult::OK,
+        UgcCallSnafu {
+            function,
+            steam_result
+        }
+    );
+
+    Ok(response)
+}
+
+pub(crate) async fn subscribe_item(
+    client: &Client,
+    published_file_id: PublishedFileId,
+) -> Result<(), UgcCallError> {
+    unsafe {
+        let handle = sys::SteamAPI_ISteamUGC_SubscribeItem(*client.0.ugc, published_file_id.into());
+
+        ugc_call_result::<sys::RemoteStorageSubscribePublishedFileResult_t>(
+            client,
+            handle,
+            "SubscribeItem",
+        )
+        .await
+    }?;
+
+    Ok(())
+}
+
+pub(crate) async fn unsubscribe_item(
+    client: &Client,
+    published_file_id: PublishedFileId,
+) -> Result<(), UgcCallError> {
+    unsafe {
+        let handle =
+            sys::SteamAPI_ISteamUGC_UnsubscribeItem(*client.0.ugc, published_file_id.into());
+
+        ugc_call_result::<sys::RemoteStorageUnsubscribePublishedFileResult_t>(
+            client,
+            handle,
+            "UnsubscribeItem",
+        )
+        .await
+    }?;
+
+    Ok(())
+}
+
+pub(crate) async fn set_user_item_vote(
+    client: &Client,
+    published_file_id: PublishedFileId,
+    vote_up: bool,
+) -> Result<(), UgcCallError> {
+    unsafe {
+        let handle = sys::SteamAPI_ISteamUGC_SetUserItemVote(
+            *client.0.ugc,
+            published_file_id.into(),
+            vote_up,
+        );
+
+        ugc_call_result::<sys::SetUserItemVoteResult_t>(client, handle, "SetUserItemVote").await
+    }?;
+
+    Ok(())
+}
+
+pub(crate) async fn get_user_item_vote(
+    client: &Client,
+    published_file_id: PublishedFileId,
+) -> Result<UserItemVote, UgcCallError> {
+    let response: sys::GetUserItemVoteResult_t = unsafe {
+        let handle =
+            sys::SteamAPI_ISteamUGC_GetUserItemVote(*client.0.ugc, published_file_id.into());
+
+        ugc_call_result(client, handle, "GetUserItemVote").await
+    }?;
+
+    let vote = if response.m_bVotedUp {
+        UserItemVote::VotedUp
+    } else if response.m_bVotedDown {
+        UserItemVote::VotedDown
+    } else if response.m_bVoteSkipped {
+        UserItemVote::Skipped
+    } else {
+        UserItemVote::NotVoted
+    };
+
+    Ok(vote)
+}
+
+pub(crate) async fn add_item_to_favorites(
+    client: &Client,
+    app_id: AppId,
+    published_file_id: PublishedFileId,
+) -> Result<(), UgcCallError> {
+    unsafe {
+        let handle = sys::SteamAPI_ISteamUGC_AddItemToFavorites(
+            *client.0.ugc,
+            app_id.into(),
+            published_file_id.into(),
+        );
+
+        ugc_call_result::<sys::UserFavoriteItemsListChanged_t>(client, handle, "AddItemToFavorites")
+            .await
+    }?;
+
+    Ok(())
+}
+
+pub(crate) async fn remove_item_from_favorites(
+    client: &Client,
+    app_id: AppId,
+    published_file_id: PublishedFileId,
+) -> Result<(), UgcCallError> {
+    unsafe {
+        let handle = sys::SteamAPI_ISteamUGC_RemoveItemFromFavorites(
+            *client.0.ugc,
+            app_id.into(),
+            published_file_id.into(),
+        );
+
+        ugc_call_result::<sys::UserFavoriteItemsListChanged_t>(
+            client,
+            handle,
+            "RemoveItemFromFavorites",
+        )
+        .await
+    }?;
+
+    Ok(())
+}
+
+pub(crate) async fn add_dependency(
+    client: &Client,
+    parent: PublishedFileId,
+    child: PublishedFileId,
+) -> Result<(), UgcCallError> {
+    unsafe {
+        let handle =
+            sys::SteamAPI_ISteamUGC_AddDependency(*client.0.ugc, parent.into(), child.into());
+
+        ugc_call_result::<sys::AddUGCDependencyResult_t>(client, handle, "AddDependency").await
+    }?;
+
+    Ok(())
+}
+
+pub(crate) async fn remove_dependency(
+    client: &Client,
+    parent: PublishedFileId,
+    child: PublishedFileId,
+) -> Result<(), UgcCallError> {
+    unsafe {
+        let handle =
+            sys::SteamAPI_ISteamUGC_RemoveDependency(*client.0.ugc, parent.into(), child.into());
+
+        ugc_call_result::<sys::RemoveUGCDependencyResult_t>(client, handle, "RemoveDependency")
+            .await
+    }?;
+
+    Ok(())
+}
+
+pub(crate) async fn add_app_dependency(
+    client: &Client,
+    published_file_id: PublishedFileId,
+    app_id: AppId,
+) -> Result<(), UgcCallError> {
+    unsafe {
+        let handle = sys::SteamAPI_ISteamUGC_AddAppDependency(
+            *client.0.ugc,
+            published_file_id.into(),
+            app_id.into(),
+        );
+
+        ugc_call_result::<sys::AddAppDependencyResult_t>(client, handle, "AddAppDependency").await
+    }?;
+
+    Ok(())
+}
+
+pub(crate) async fn remove_app_dependency(
+    client: &Client,
+    published_file_id: PublishedFileId,
+    app_id: AppId,
+) -> Result<(), UgcCallError> {
+    unsafe {
+        let handle = sys::SteamAPI_ISteamUGC_RemoveAppDependency(
+            *client.0.ugc,
+            published_file_id.into(),
+            app_id.into(),
+        );
+
+        ugc_call_result::<sys::RemoveAppDependencyResult_t>(client, handle, "RemoveAppDependency")
+            .await
+    }?;
+
+    Ok(())
+}
+
+pub(crate) async fn delete_item(
+    client: &Client,
+    published_file_id: PublishedFileId,
+) -> Result<(), UgcCallError> {
+    unsafe {
+        let handle = sys::SteamAPI_ISteamUGC_DeleteItem(*client.0.ugc, published_file_id.into());
+
+        ugc_call_result::<sys::DeleteItemResult_t>(client, handle, "DeleteItem").await
+    }?;
+
+    Ok(())
+}
+
+/// The maximum number of items that can be passed to a single playtime tracking call.
+const MAX_PLAYTIME_TRACKING_ITEMS: usize = 100;
+
+pub(crate) async fn start_playtime_tracking(
+    client: &Client,
+    published_file_ids: Vec<PublishedFileId>,
+) -> Result<(), UgcCallError> {
+    for chunk in published_file_ids.chunks(MAX_PLAYTIME_TRACKING_ITEMS) {
+        let mut ids: Vec<sys::PublishedFileId_t> = chunk.iter().map(|x| x.0).collect();
+        unsafe {
+            let handle = sys::SteamAPI_ISteamUGC_StartPlaytimeTracking(
+                *client.0.ugc,
+                ids.as_mut_ptr(),
+                u32::try_from(ids.len()).unwrap(),
+            );
+
+            ugc_call_result::<sys::StartPlaytimeTrackingResult_t>(
+                client,
+                handle,
+                "StartPlaytimeTracking",
+            )
+            .await
+        }?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn stop_playtime_tracking(
+    client: &Client,
+    published_file_ids: Vec<PublishedFileId>,
+) -> Result<(), UgcCallError> {
+    for chunk in published_file_ids.chunks(MAX_PLAYTIME_TRACKING_ITEMS) {
+        let mut ids: Vec<sys::PublishedFileId_t> = chunk.iter().map(|x| x.0).collect();
+        unsafe {
+            let handle = sys::SteamAPI_ISteamUGC_StopPlaytimeTracking(
+                *client.0.ugc,
+                ids.as_mut_ptr(),
+                u32::try_from(ids.len()).unwrap(),
+            );
+
+            ugc_call_result::<sys::StopPlaytimeTrackingResult_t>(
+                client,
+                handle,
+                "StopPlaytimeTracking",
+            )
+            .await
+        }?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn stop_playtime_tracking_for_all_items(
+    client: &Client,
+) -> Result<(), UgcCallError> {
+    unsafe {
+        let handle = sys::SteamAPI_ISteamUGC_StopPlaytimeTrackingForAllItems(*client.0.ugc);
+
+        ugc_call_result::<sys::StopPlaytimeTrackingResult_t>(
+            client,
+            handle,
+            "StopPlaytimeTrackingForAllItems",
+        )
+        .await
+    }?;
+
+    Ok(())
+}
+
+pub(crate) fn subscribed_items(client: &Client) -> Vec<PublishedFileId> {
+    unsafe {
+        let count = sys::SteamAPI_ISteamUGC_GetNumSubscribedItems(*client.0.ugc);
+        let mut items = vec![0; count as usize];
+        let written =
+            sys::SteamAPI_ISteamUGC_GetSubscribedItems(*client.0.ugc, items.as_mut_ptr(), count);
+        items.truncate(written as usize);
+
+        items.into_iter().map(PublishedFileId).collect()
+    }
+}
+
+pub(crate) fn item_state(client: &Client, published_file_id: PublishedFileId) -> ItemState {
+    let state =
+        unsafe { sys::SteamAPI_ISteamUGC_GetItemState(*client.0.ugc, published_file_id.into()) };
+
+    ItemState::from_bits_truncate(state)
+}
+
+pub(crate) fn item_install_info(
+    client: &Client,
+    published_file_id: PublishedFileId,
+) -> Option<ItemInstallInfo> {
+    let mut size_on_disk = 0;
+    let mut folder = vec![0_u8; 4096];
+    let mut timestamp = 0;
+    let success = unsafe {
+        sys::SteamAPI_ISteamUGC_GetItemInstallInfo(
+            *client.0.ugc,
+            published_file_id.into(),
+            &mut size_on_disk,
+            folder.as_mut_ptr() as *mut c_char,
+            u32::try_from(folder.len()).unwrap(),
+            &mut timestamp,
+        )
+    };
+
+    if success {
+        Some(ItemInstallInfo {
+            folder: String::from_utf8_nul_truncating(folder)
+                .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+                .into(),
+            size_on_disk,
+            timestamp: Utc.timestamp_opt(i64::from(timestamp), 0).unwrap(),
+        })
+    } else {
+        None
+    }
+}
+
+pub(crate) async fn download_item(
+    client: &Client,
+    published_file_id: PublishedFileId,
+    high_priority: bool,
+) -> Result<(), DownloadItemError> {
+    // Listen for the result before starting the download, so that it can't be missed
+    let results = client
+        .on_download_item_result()
+        .filter(|result| future::ready(result.published_file_id == published_file_id));
+    pin_mut!(results);
+
+    let started = unsafe {
+        sys::SteamAPI_ISteamUGC_DownloadItem(*client.0.ugc, published_file_id.into(), high_priority)
+    };
+    ensure!(started, download_item_error::DownloadItemSnafu);
+
+    let result = results
+        .next()
+        .await
+        .expect("The DownloadItemResult_t callback stream ended unexpectedly");
+    ensure!(
+        result.steam_result == SteamResult::OK,
+        download_item_error::DownloadSnafu {
+            steam_result: result.steam_result
+        }
+    );
+
+    Ok(())
+}
+
+pub(crate) fn item_download_progress(
+    client: &Client,
+    published_file_id: PublishedFileId,
+) -> Option<ItemDownloadProgress> {
+    let mut bytes_downloaded = 0;
+    let mut bytes_total = 0;
+    let success = unsafe {
+        sys::SteamAPI_ISteamUGC_GetItemDownloadInfo(
+            *client.0.ugc,
+            published_file_id.into(),
+            &mut bytes_downloaded,
+            &mut bytes_total,
+        )
+    };
+
+    if success {
+        Some(ItemDownloadProgress {
+            bytes_downloaded,
+            bytes_total,
+        })
+    } else {
+        None
+    }
+}
+
+pub(crate) fn item_download_progress_updates(
+    client: Client,
+    published_file_id: PublishedFileId,
+    interval: Duration,
+) -> impl Stream<Item = ItemDownloadProgress> + Send {
+    // Listen for the result right away, so that the end of the download can't be missed
+    let results = client
+        .on_download_item_result()
+        .filter(move |result| future::ready(result.published_file_id == published_file_id));
+
+    Gen::new(|co| async move {
+        pin_mut!(results);
+        loop {
+            if let Some(progress) = item_download_progress(&client, published_file_id) {
+                co.yield_(progress).await;
+                if progress.is_complete() {
+                    break;
+                }
+            }
+
+            let delay = client.delay(interval);
+            pin_mut!(delay);
+            if let Either::Right(_) = future::select(delay, results.next()).await {
+                break;
+            }
+        }
+    })
+}
+
+fn item_update_progress(
+    client: &Client,
+    handle: sys::UGCUpdateHandle_t,
+) -> Option<ItemUpdateProgress> {
+    let mut bytes_processed = 0;
+    let mut bytes_total = 0;
+    let status = unsafe {
+        sys::SteamAPI_ISteamUGC_GetItemUpdateProgress(
+            *client.0.ugc,
+            handle,
+            &mut bytes_processed,
+            &mut bytes_total,
+        )
+    };
+
+    ItemUpdateStatus::from_i32(status as i32).map(|status| ItemUpdateProgress {
+        status,
+        bytes_processed,
+        bytes_total,
+    })
+}
+
+/// A UGC query handle, which is released when dropped. This makes sure the handle isn't leaked
+/// when a query fails, or when the future running it is dropped.
+struct QueryHandle {
+    client: Client,
+    handle: sys::UGCQueryHandle_t,
+}
+
+impl Drop for QueryHandle {
+    fn drop(&mut self) {
+        unsafe { sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(*self.client.0.ugc, self.handle) };
+    }
+}
+
+/// The errors of a paged query type that can occur while sending a page and reading its results.
+trait PagedQueryError: Sized {
+    fn send_query_failed(steam_result: SteamResult) -> Self;
+    fn read_result_failed(e: QueryResultError) -> Self;
+}
+
+macro_rules! impl_paged_query_error {
+    ($error:ident, $module:ident) => {
+        impl PagedQueryError for $error {
+            fn send_query_failed(steam_result: SteamResult) -> Self {
+                $module::SendQueryUGCRequestSnafu { steam_result }.build()
+            }
+
+            fn read_result_failed(e: QueryResultError) -> Self {
+                match e {
+                    QueryResultError::InvalidUtf8(e) => $module::InvalidUtf8Snafu {
+                        published_file_id: e.published_file_id,
+                        field: e.field,
+                    }
+                    .build(),
+                    QueryResultError::Function(e) => $module::ReadQueryResultSnafu {
+                        function: e.function,
+                    }
+                    .build(),
+                }
+            }
+        }
+    };
+}
+
+impl_paged_query_error!(QueryAllUgcError, query_all_ugc_error);
+impl_paged_query_error!(QueryUserUgcError, query_user_ugc_error);
+
+/// Sends one page of a paged query and yields up to `max_results` of its results, returning the
+/// completed query. If sending fails, the error is yielded instead and `None` is returned.
+async fn yield_query_page<E: PagedQueryError>(
+    co: &Co<Result<UgcDetails, E>>,
+    query: QueryHandle,
+    config: &QueryConfig,
+    max_results: u32,
+) -> Option<sys::SteamUGCQueryCompleted_t> {
+    let response = match send_query(&query).await {
+        Ok(x) => x,
+        Err(steam_result) => {
+            co.yield_(Err(E::send_query_failed(steam_result))).await;
+            return None;
+        }
+    };
+
+    for i in 0..cmp::min(max_results, response.m_unNumResultsReturned) {
+        let details = unsafe {
+            raw_query_result(&query.client, response.m_handle, i)
+                .map_err(QueryResultError::from)
+                .and_then(|raw| {
+                    query_result_details(&query.client, response.m_handle, i, &raw, config)
+                })
+        };
+        co.yield_(details.map_err(E::read_result_failed)).await;
+    }
+
+    Some(response)
+}
+
+/// Sends a configured query, returning the completed query on success.
+async fn send_query(query: &QueryHandle) -> Result<sys::SteamUGCQueryCompleted_t, SteamResult> {
+    let response: sys::SteamUGCQueryCompleted_t = unsafe {
+        let handle = sys::SteamAPI_ISteamUGC_SendQueryUGCRequest(*query.client.0.ugc, query.handle);
+
+        query.client.register_for_call_result(handle).await
+    };
+
+    let result = SteamResult::from_inner(response.m_eResult);
+    if result == SteamResult::OK {
+        Ok(response)
+    } else {
+        Err(result)
+    }
+}
+
+/// Reads the raw details of the query result at `index`.
+///
+/// # Safety
+///
+/// `handle` must be the handle of a completed query, with more than `index` results.
+unsafe fn raw_query_result(
+    client: &Client,
+    handle: sys::UGCQueryHandle_t,
+    index: u32,
+) -> Result<sys::SteamUGCDetails_t, QueryFunctionFailed> {
+    let mut details: MaybeUninit<sys::SteamUGCDetails_t> = MaybeUninit::uninit();
+    let success = sys::SteamAPI_ISteamUGC_GetQueryUGCResult(
+        *client.0.ugc,
+        handle,
+        index,
+        details.as_mut_ptr(),
+    );
+    check_query_function(success, "GetQueryUGCResult")?;
+    Ok(details.assume_init())
+}
+
+/// Reads the details of the query result at `index`, given its already read raw `details`.
+///
+/// # Safety
+///
+/// `handle` must be the handle of a completed query, with more than `index` results.
+#[allow(deprecated)]
+unsafe fn query_result_details(
+    client: &Client,
+    handle: sys::UGCQueryHandle_t,
+    index: u32,
+    details: &sys::SteamUGCDetails_t,
+    config: &QueryConfig,
+) -> Result<UgcDetails, QueryResultError> {
+    let ugc = *client.0.ugc;
+    let decoder = FieldDecoder {
+        published_file_id: PublishedFileId(details.m_nPublishedFileId),
+        lossy: config.lossy_utf8,
+    };
+    let preview_url = {
+        let mut buf = vec![0_u8; 256];
+        sys::SteamAPI_ISteamUGC_GetQueryUGCPreviewURL(
+            *client.0.ugc,
+            handle,
+            index,
+            buf.as_mut_ptr() as *mut c_char,
+            u32::try_from(buf.len()).unwrap(),
+        );
+        decoder.decode(String::from_utf8_nul_truncating(buf), "preview URL")?
+    };
+
+    Ok(UgcDetails {
+        published_file_id: PublishedFileId(details.m_nPublishedFileId),
+        file_type: WorkshopFileType::from_inner(details.m_eFileType),
+        creator_app_id: AppId(details.m_nCreatorAppID),
+        title: decoder.decode(
+            String::from_utf8_nul_truncating(&details.m_rgchTitle[..]),
+            "title",
+        )?,
+        description: decoder.decode(
+            String::from_utf8_nul_truncating(&details.m_rgchDescription[..]),
+            "description",
+        )?,
+        steam_id_owner: details.m_ulSteamIDOwner.into(),
+        time_created: Utc
+            .timestamp_opt(i64::from(details.m_rtimeCreated), 0)
+            .unwrap(),
+        time_updated: Utc
+            .timestamp_opt(i64::from(details.m_rtimeUpdated), 0)
+            .unwrap(),
+        time_added_to_user_list: if details.m_rtimeAddedToUserList == 0 {
+            None
+        } else {
+            Some(
+                Utc.timestamp_opt(i64::from(details.m_rtimeAddedToUserList), 0)
+                    .unwrap(),
+            )
+        },
+        visibility: PublishedFileVisibility::from_inner(details.m_eVisibility),
+        banned: details.m_bBanned,
+        accepted_for_use: details.m_bAcceptedForUse,
+        tags_truncated: details.m_bTagsTruncated,
+        tags: Tags::from_raw(
+            &decoder.decode(
+                String::from_utf8_nul_truncating(&details.m_rgchTags[..]),
+                "tags",
+            )?,
+            details.m_bTagsTruncated,
+        ),
+        file: UgcHandle::from_inner(details.m_hFile),
+        preview_file: UgcHandle::from_inner(details.m_hPreviewFile),
+        preview_url,
+        file_name: decoder.decode(
+            String::from_utf8_nul_truncating(&details.m_pchFileName[..]),
+            "file name",
+        )?,
+        file_size: details.m_nFileSize,
+        preview_file_size: details.m_nPreviewFileSize,
+        url: decoder.decode(
+            String::from_utf8_nul_truncating(&details.m_rgchURL[..]),
+            "URL",
+        )?,
+        votes_up: details.m_unVotesUp,
+        votes_down: details.m_unVotesDown,
+        score: details.m_flScore,
+        num_children: details.m_unNumChildren,
+        key_value_tags: if config.return_key_value_tags {
+            query_result_key_value_tags(ugc, handle, index, &decoder)?
+        } else {
+            Vec::new()
+        },
+        metadata: if config.return_metadata {
+            let mut buf = vec![0_u8; sys::k_cchDeveloperMetadataMax as usize + 1];
+            sys::SteamAPI_ISteamUGC_GetQueryUGCMetadata(
+                ugc,
+                handle,
+                index,
+                buf.as_mut_ptr() as *mut c_char,
+                u32::try_from(buf.len()).unwrap(),
+            );
+            decoder.decode(String::from_utf8_nul_truncating(buf), "metadata")?
+        } else {
+            String::new()
+        },
+        children: if config.return_children {
+            let mut children = vec![0; details.m_unNumChildren as usize];
+            let success = sys::SteamAPI_ISteamUGC_GetQueryUGCChildren(
+                ugc,
+                handle,
+                index,
+                children.as_mut_ptr(),
+                details.m_unNumChildren,
+            );
+            check_query_function(success, "GetQueryUGCChildren")?;
+            children.into_iter().map(PublishedFileId).collect()
+        } else {
+            Vec::new()
+        },
+        additional_previews: if config.return_additional_previews {
+            query_result_additional_previews(ugc, handle, index, &decoder)?
+        } else {
+            Vec::new()
+        },
+        statistics: query_result_statistics(ugc, handle, index),
+    })
+}
+
+unsafe fn query_result_key_value_tags(
+    ugc: *mut sys::ISteamUGC,
+    handle: sys::UGCQueryHandle_t,
+    index: u32,
+    decoder: &FieldDecoder,
+) -> Result<Vec<(String, String)>, QueryResultError> {
+    let count = sys::SteamAPI_ISteamUGC_GetQueryUGCNumKeyValueTags(ugc, handle, index);
+    (0..count)
+        .map(|i| {
+            let mut key = vec![0_u8; 256];
+            let mut value = vec![0_u8; 256];
+            let success = sys::SteamAPI_ISteamUGC_GetQueryUGCKeyValueTag(
+                ugc,
+                handle,
+                index,
+                i,
+                key.as_mut_ptr() as *mut c_char,
+                u32::try_from(key.len()).unwrap(),
+                value.as_mut_ptr() as *mut c_char,
+                u32::try_from(value.len()).unwrap(),
+            );
+            check_query_function(success, "GetQueryUGCKeyValueTag")?;
+            Ok((
+                decoder.decode(String::from_utf8_nul_truncating(key), "key-value tag key")?,
+                decoder.decode(
+                    String::from_utf8_nul_truncating(value),
+                    "key-value tag value",
+                )?,
+            ))
+        })
+        .collect()
+}
+
+unsafe fn query_result_additional_previews(
+    ugc: *mut sys::ISteamUGC,
+    handle: sys::UGCQueryHandle_t,
+    index: u32,
+    decoder: &FieldDecoder,
+) -> Result<Vec<AdditionalPreview>, QueryResultError> {
+    let count = sys::SteamAPI_ISteamUGC_GetQueryUGCNumAdditionalPreviews(ugc, handle, index);
+    let mut previews = Vec::with_capacity(count as usize);
+    for i in 0..count {
+        let mut url_or_video_id = vec![0_u8; sys::k_cchPublishedFileURLMax as usize];
+        let mut original_file_name = vec![0_u8; sys::k_cchFilenameMax as usize];
+        let mut preview_type = sys::EItemPreviewType_k_EItemPreviewType_Image;
+        let success = sys::SteamAPI_ISteamUGC_GetQueryUGCAdditionalPreview(
+            ugc,
+            handle,
+            index,
+            i,
+            url_or_video_id.as_mut_ptr() as *mut c_char,
+            u32::try_from(url_or_video_id.len()).unwrap(),
+            original_file_name.as_mut_ptr() as *mut c_char,
+            u32::try_from(original_file_name.len()).unwrap(),
+            &mut preview_type,
+        );
+        check_query_function(success, "GetQueryUGCAdditionalPreview")?;
+
+        // Skip previews of types we can't represent rather than failing the whole query
+        let preview_type = match ItemPreviewType::from_inner(preview_type) {
+            Some(preview_type) => preview_type,
+            None => continue,
+        };
+        previews.push(AdditionalPreview {
+            url_or_video_id: decoder.decode(
+                String::from_utf8_nul_truncating(url_or_video_id),
+                "additional preview URL",
+            )?,
+            original_file_name: decoder.decode(
+                String::from_utf8_nul_truncating(original_file_name),
+                "additional preview file name",
+            )?,
+            preview_type,
+        });
+    }
+
+    Ok(previews)
+}
+
+unsafe fn query_result_statistics(
+    ugc: *mut sys::ISteamUGC,
+    handle: sys::UGCQueryHandle_t,
+    index: u32,
+) -> ItemStatistics {
+    let statistic = |stat_type: sys::EItemStatistic| {
+        let mut value = 0;
+        if sys::SteamAPI_ISteamUGC_GetQueryUGCStatistic(ugc, handle, index, stat_type, &mut value) {
+            value
+        } else {
+            0
+        }
+    };
+
+    ItemStatistics {
+        num_subscriptions: statistic(sys::EItemStatistic_k_EItemStatistic_NumSubscriptions),
+        num_favorites: statistic(sys::EItemStatistic_k_EItemStatistic_NumFavorites),
+        num_followers: statistic(sys::EItemStatistic_k_EItemStatistic_NumFollowers),
+        num_unique_subscriptions: statistic(
+            sys::EItemStatistic_k_EItemStatistic_NumUniqueSubscriptions,
+        ),
+        num_unique_favorites: statistic(sys::EItemStatistic_k_EItemStatistic_NumUniqueFavorites),
+        num_unique_followers: statistic(sys::EItemStatistic_k_EItemStatistic_NumUniqueFollowers),
+        num_unique_website_views: statistic(
+            sys::EItemStatistic_k_EItemStatistic_NumUniqueWebsiteViews,
+        ),
+        report_score: statistic(sys::EItemStatistic_k_EItemStatistic_ReportScore),
+        num_seconds_played: statistic(sys::EItemStatistic_k_EItemStatistic_NumSecondsPlayed),
+        num_playtime_sessions: statistic(sys::EItemStatistic_k_EItemStatistic_NumPlaytimeSessions),
+        num_comments: statistic(sys::EItemStatistic_k_EItemStatistic_NumComments),
+        num_seconds_played_during_time_period: statistic(
+            sys::EItemStatistic_k_EItemStatistic_NumSecondsPlayedDuringTimePeriod,
+        ),
+        num_playtime_sessions_during_time_period: statistic(
+            sys::EItemStatistic_k_EItemStatistic_NumPlaytimeSessionsDuringTimePeriod,
+        ),
+    }
 }
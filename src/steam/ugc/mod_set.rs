@@ -0,0 +1,212 @@
+use crate::steam::ugc::{
+    DownloadItemError, ItemState, PublishedFileId, QueryUgcDetailsError, UgcItemError,
+};
+use crate::Client;
+use chrono::{DateTime, Utc};
+use snafu::{OptionExt, ResultExt};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// A set of workshop items that should be installed together, such as the mods used by a
+/// dedicated server.
+///
+/// Resolving the set fetches the details of the items and everything they depend on, downloads
+/// any items that are missing or out of date, and produces a [`ModManifest`] describing exactly
+/// what is installed.
+///
+/// # Example
+///
+/// ```no_run
+/// # let client: steamworks::Client = unimplemented!();
+/// use steamworks::ugc::{ModSet, PublishedFileId};
+///
+/// # async {
+/// let manifest = ModSet::new(client, [PublishedFileId(1234), PublishedFileId(5678)])
+///     .resolve()
+///     .await?;
+/// for entry in &manifest.entries {
+///     println!("{:?} -> {}", entry.published_file_id, entry.install_path.display());
+/// }
+/// # Ok::<(), steamworks::ugc::ModSetError>(())
+/// # };
+/// ```
+#[derive(Debug, Clone)]
+pub struct ModSet {
+    client: Client,
+    published_file_ids: Vec<PublishedFileId>,
+    high_priority: bool,
+}
+
+impl ModSet {
+    pub fn new(client: Client, published_file_ids: impl Into<Vec<PublishedFileId>>) -> Self {
+        ModSet {
+            client,
+            published_file_ids: published_file_ids.into(),
+            high_priority: true,
+        }
+    }
+
+    /// Sets whether missing items are downloaded with high priority, pausing other downloads.
+    ///
+    /// Defaults to `true`.
+    pub fn high_priority(self, high_priority: bool) -> Self {
+        ModSet {
+            high_priority,
+            ..self
+        }
+    }
+
+    /// Resolves the dependencies of the set, downloads any items that are missing or out of date,
+    /// and returns a manifest of the installed items.
+    ///
+    /// Items whose details can't be retrieved, for example because they were deleted or made
+    /// private, don't fail the whole set; they are left out of the entries and listed in
+    /// [`ModManifest::unavailable_items`] instead.
+    pub async fn resolve(self) -> Result<ModManifest, ModSetError> {
+        let client = &self.client;
+
+        // Walk the dependency graph breadth-first, one UGC details query per level
+        let mut resolved: BTreeSet<PublishedFileId> = BTreeSet::new();
+        let mut unavailable_items = Vec::new();
+        let mut pending: BTreeSet<PublishedFileId> =
+            self.published_file_ids.iter().copied().collect();
+        while !pending.is_empty() {
+            let ids: Vec<PublishedFileId> = pending.iter().copied().collect();
+            let results = client
+                .query_ugc_details(&ids)
+                .return_children()
+                .run()
+                .await
+                .context(mod_set_error::QueryUgcDetailsSnafu)?;
+
+            pending.clear();
+            for result in results {
+                let details = match result {
+                    Ok(x) => x,
+                    Err(e) => {
+                        unavailable_items.push(e);
+                        continue;
+                    }
+                };
+                resolved.insert(details.published_file_id);
+                pending.extend(
+                    details
+                        .children
+                        .into_iter()
+                        .filter(|child| !resolved.contains(child)),
+                );
+            }
+        }
+
+        let mut entries = Vec::with_capacity(resolved.len());
+        for published_file_id in resolved {
+            let state = client.item_state(published_file_id);
+            if !state.contains(ItemState::INSTALLED) || state.contains(ItemState::NEEDS_UPDATE) {
+                client
+                    .download_item(published_file_id, self.high_priority)
+                    .await
+                    .context(mod_set_error::DownloadItemSnafu { published_file_id })?;
+            }
+
+            let install_info = client
+                .item_install_info(published_file_id)
+                .context(mod_set_error::NotInstalledSnafu { published_file_id })?;
+            entries.push(ModManifestEntry {
+                published_file_id,
+                time_updated: install_info.timestamp,
+                install_path: install_info.folder,
+            });
+        }
+
+        Ok(ModManifest {
+            entries,
+            unavailable_items,
+        })
+    }
+}
+
+/// A description of an installed [`ModSet`].
+///
+/// With the `serde` feature enabled, the manifest implements `Serialize` and `Deserialize`, so it
+/// can be stored in whichever format suits the application. A stored manifest can later be
+/// checked with [`verify`](Self::verify), or with [`missing_items`](Self::missing_items) when
+/// Steam isn't available.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModManifest {
+    /// The installed items, ordered by ID.
+    pub entries: Vec<ModManifestEntry>,
+
+    /// The items, or dependencies of items, whose details couldn't be retrieved. These aren't
+    /// serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unavailable_items: Vec<UgcItemError>,
+}
+
+impl ModManifest {
+    /// Returns the items that are no longer installed as recorded in the manifest: items that
+    /// Steam reports as not installed or needing an update, items installed to a different
+    /// folder, items whose installed version has a different update time, and items whose
+    /// install folder doesn't exist.
+    pub fn verify(&self, client: &Client) -> Vec<PublishedFileId> {
+        self.entries
+            .iter()
+            .filter(|entry| {
+                let state = client.item_state(entry.published_file_id);
+                if !state.contains(ItemState::INSTALLED) || state.contains(ItemState::NEEDS_UPDATE)
+                {
+                    return true;
+                }
+
+                match client.item_install_info(entry.published_file_id) {
+                    Some(info) => {
+                        info.timestamp != entry.time_updated
+                            || info.folder != entry.install_path
+                            || !entry.install_path.is_dir()
+                    }
+                    None => true,
+                }
+            })
+            .map(|entry| entry.published_file_id)
+            .collect()
+    }
+
+    /// Returns the items whose install folder doesn't exist. This doesn't require Steam to be
+    /// running, but only checks for the folder, so it won't notice an outdated or partially
+    /// removed item; use [`verify`](Self::verify) for that.
+    pub fn missing_items(&self) -> Vec<PublishedFileId> {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.install_path.is_dir())
+            .map(|entry| entry.published_file_id)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModManifestEntry {
+    pub published_file_id: PublishedFileId,
+    /// When the installed version of the item was last updated.
+    pub time_updated: DateTime<Utc>,
+    pub install_path: PathBuf,
+}
+
+#[derive(Debug, snafu::Snafu)]
+#[snafu(module)]
+pub enum ModSetError {
+    /// Querying the details of the items failed
+    #[snafu(display("Querying the details of the items failed: {}", source))]
+    QueryUgcDetails { source: QueryUgcDetailsError },
+
+    /// Downloading one of the items failed
+    #[snafu(display("Downloading workshop item {} failed: {}", published_file_id.0, source))]
+    DownloadItem {
+        published_file_id: PublishedFileId,
+        source: DownloadItemError,
+    },
+
+    /// One of the items isn't installed, even though it was downloaded
+    #[snafu(display("Workshop item {} isn't installed", published_file_id.0))]
+    NotInstalled { published_file_id: PublishedFileId },
+}
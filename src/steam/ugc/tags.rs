@@ -0,0 +1,258 @@
+use snafu::ensure;
+use std::collections::{btree_set, BTreeSet};
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::fmt::{self, Display};
+use std::iter;
+use std::os::raw::c_char;
+use steamworks_sys as sys;
+
+/// The maximum length of a single tag, in bytes.
+pub const MAX_TAG_LENGTH: usize = 255;
+
+/// A set of workshop item tags.
+///
+/// Tags are trimmed of surrounding whitespace when inserted, and are validated against Steam's
+/// limits: a tag can't be empty, can't be longer than [`MAX_TAG_LENGTH`] bytes, and can't contain
+/// commas or control characters.
+///
+/// # Example
+///
+/// ```
+/// use steamworks::ugc::Tags;
+///
+/// let mut tags = Tags::try_from_iter(["Map", "Co-op"])?;
+/// tags.insert("Co-op")?;
+/// assert!(tags.contains("Map"));
+/// assert_eq!(tags.len(), 2);
+/// assert!(tags.insert("Bad,Tag").is_err());
+/// # Ok::<(), steamworks::ugc::InvalidTagError>(())
+/// ```
+#[derive(Debug, Clone, Default, Hash, Eq, PartialEq)]
+pub struct Tags {
+    tags: BTreeSet<String>,
+    truncated: bool,
+}
+
+impl Tags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a set from the given tags, failing on the first invalid tag.
+    pub fn try_from_iter<T: Into<String>>(
+        tags: impl IntoIterator<Item = T>,
+    ) -> Result<Self, InvalidTagError> {
+        let mut set = Tags::new();
+        for tag in tags {
+            set.insert(tag)?;
+        }
+
+        Ok(set)
+    }
+
+    /// Adds a tag to the set, returning whether it wasn't already present.
+    pub fn insert(&mut self, tag: impl Into<String>) -> Result<bool, InvalidTagError> {
+        let tag = tag.into();
+        let tag = tag.trim();
+        ensure!(!tag.is_empty(), invalid_tag_error::EmptySnafu);
+        ensure!(
+            tag.len() <= MAX_TAG_LENGTH,
+            invalid_tag_error::TooLongSnafu { tag }
+        );
+        if let Some(character) = tag.chars().find(|&c| c == ',' || c.is_control()) {
+            return invalid_tag_error::InvalidCharacterSnafu { tag, character }.fail();
+        }
+
+        Ok(self.tags.insert(tag.to_owned()))
+    }
+
+    /// Removes a tag from the set, returning whether it was present.
+    pub fn remove(&mut self, tag: &str) -> bool {
+        self.tags.remove(tag.trim())
+    }
+
+    pub fn contains(&self, tag: &str) -> bool {
+        self.tags.contains(tag.trim())
+    }
+
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Whether Steam truncated the tags of a queried item, in which case some of the item's tags
+    /// are missing from this set.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.into_iter()
+    }
+
+    /// Returns the tags as a comma-separated list.
+    #[deprecated(note = "use the `Display` implementation or `iter()` instead")]
+    pub fn into_inner(self) -> String {
+        self.to_string()
+    }
+
+    /// Parses the comma-separated tag list returned by Steam.
+    pub(crate) fn from_raw(raw: &str, truncated: bool) -> Self {
+        let tags = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_owned)
+            .collect();
+
+        Tags { tags, truncated }
+    }
+
+    pub(crate) fn to_param_string_array(&self) -> ParamStringArray {
+        ParamStringArray::new(
+            self.tags
+                .iter()
+                .map(|tag| CString::new(tag.as_str()).expect("Tag contains nul byte(s)"))
+                .collect(),
+        )
+    }
+}
+
+impl<'a> IntoIterator for &'a Tags {
+    type Item = &'a str;
+    type IntoIter = iter::Map<btree_set::Iter<'a, String>, fn(&String) -> &str>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tags.iter().map(String::as_str)
+    }
+}
+
+/// Formats the tags as a comma-separated list.
+impl Display for Tags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        for (i, tag) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(tag)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, snafu::Snafu)]
+#[snafu(module)]
+pub enum InvalidTagError {
+    /// The tag is empty
+    #[snafu(display("The tag is empty"))]
+    Empty,
+
+    /// The tag is too long
+    #[snafu(display(
+        "The tag {:?} has a length of {} bytes, which is over the {} byte limit",
+        tag,
+        tag.len(),
+        MAX_TAG_LENGTH
+    ))]
+    TooLong { tag: String },
+
+    /// The tag contains a character that isn't allowed in tags
+    #[snafu(display("The tag {:?} contains the disallowed character {:?}", tag, character))]
+    InvalidCharacter { tag: String, character: char },
+}
+
+/// An owned list of strings, which can be passed to the Steamworks API as a
+/// `SteamParamStringArray_t`.
+pub(crate) struct ParamStringArray {
+    _strings: Vec<CString>,
+    pointers: Vec<*const c_char>,
+}
+
+impl ParamStringArray {
+    pub(crate) fn new(strings: Vec<CString>) -> Self {
+        let pointers = strings.iter().map(|x| x.as_ptr()).collect();
+        ParamStringArray {
+            _strings: strings,
+            pointers,
+        }
+    }
+
+    /// The returned value borrows from `self`, so it must not outlive it.
+    pub(crate) fn as_raw(&mut self) -> sys::SteamParamStringArray_t {
+        sys::SteamParamStringArray_t {
+            m_ppStrings: self.pointers.as_mut_ptr(),
+            m_nNumStrings: i32::try_from(self.pointers.len()).unwrap(),
+        }
+    }
+}
+
+#[test]
+fn test_tags_from_raw() {
+    let tags = Tags::from_raw(" Map,Co-op,,Map , Singleplayer", false);
+    assert_eq!(tags.to_string(), "Co-op,Map,Singleplayer");
+    assert!(!tags.is_truncated());
+
+    assert!(Tags::from_raw("", true).is_empty());
+}
+
+#[test]
+fn test_tags_insert() {
+    let mut tags = Tags::new();
+    assert_eq!(tags.insert(" Map "), Ok(true));
+    assert_eq!(tags.insert("Map"), Ok(false));
+    assert!(tags.contains("Map"));
+    assert_eq!(tags.len(), 1);
+
+    assert_eq!(tags.insert(""), Err(InvalidTagError::Empty));
+    assert_eq!(tags.insert("  "), Err(InvalidTagError::Empty));
+    assert_eq!(tags.insert("a".repeat(MAX_TAG_LENGTH)), Ok(true));
+    assert_eq!(
+        tags.insert("a".repeat(MAX_TAG_LENGTH + 1)),
+        Err(InvalidTagError::TooLong {
+            tag: "a".repeat(MAX_TAG_LENGTH + 1)
+        })
+    );
+    assert_eq!(
+        tags.insert("Co,op"),
+        Err(InvalidTagError::InvalidCharacter {
+            tag: "Co,op".to_owned(),
+            character: ','
+        })
+    );
+    assert_eq!(
+        tags.insert("Co\0op"),
+        Err(InvalidTagError::InvalidCharacter {
+            tag: "Co\0op".to_owned(),
+            character: '\0'
+        })
+    );
+    assert_eq!(tags.len(), 2);
+}
+
+#[test]
+fn test_tags_to_param_string_array() {
+    use std::ffi::CStr;
+
+    let tags = Tags::try_from_iter(["Map", "Co-op"]).unwrap();
+    let mut array = tags.to_param_string_array();
+    let raw = array.as_raw();
+    assert_eq!(raw.m_nNumStrings, 2);
+    let strings: Vec<&str> = (0..2)
+        .map(|i| {
+            unsafe { CStr::from_ptr(*raw.m_ppStrings.add(i)) }
+                .to_str()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(strings, ["Co-op", "Map"]);
+
+    assert_eq!(
+        Tags::new().to_param_string_array().as_raw().m_nNumStrings,
+        0
+    );
+}